@@ -0,0 +1,150 @@
+use serde::Serialize;
+
+use crate::constants::{INVITE_MAX_AGE, INVITE_MAX_USES};
+use crate::internal::prelude::*;
+
+use super::ValidationError;
+
+/// A builder for the body of an invite to be created for a channel.
+///
+/// By default the invite expires after 24 hours and can be used an unlimited
+/// number of times.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::CreateInvite;
+///
+/// let mut invite = CreateInvite::new();
+/// invite.max_age(3600).max_uses(1).unique(true);
+///
+/// assert!(invite.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateInvite {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_age: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_uses: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temporary: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unique: Option<bool>,
+}
+
+impl CreateInvite {
+    /// Creates an invite with the default settings.
+    pub fn new() -> CreateInvite {
+        CreateInvite::default()
+    }
+
+    /// Sets the duration in seconds after which the invite expires, `0` for
+    /// never.
+    ///
+    /// The duration can be at most 604800 seconds (7 days).
+    pub fn max_age(&mut self, max_age: u64) -> &mut Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the maximum number of times the invite can be used, `0` for
+    /// unlimited.
+    ///
+    /// The invite can be used at most 100 times.
+    pub fn max_uses(&mut self, max_uses: u64) -> &mut Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Sets whether the invite only grants temporary membership.
+    pub fn temporary(&mut self, temporary: bool) -> &mut Self {
+        self.temporary = Some(temporary);
+        self
+    }
+
+    /// Sets whether a new unique invite is created, instead of reusing a
+    /// similar invite.
+    pub fn unique(&mut self, unique: bool) -> &mut Self {
+        self.unique = Some(unique);
+        self
+    }
+
+    /// Validates the invite against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the maximum age is longer than
+    /// [`INVITE_MAX_AGE`] seconds, or the maximum number of uses is greater
+    /// than [`INVITE_MAX_USES`].
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`INVITE_MAX_AGE`]: ../constants/constant.INVITE_MAX_AGE.html
+    /// [`INVITE_MAX_USES`]: ../constants/constant.INVITE_MAX_USES.html
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        match self.max_age {
+            Some(max_age) if max_age > INVITE_MAX_AGE => {
+                return Err(ValidationError::InviteMaxAgeTooLong {
+                    max_age,
+                    max: INVITE_MAX_AGE,
+                });
+            }
+            _ => {}
+        }
+
+        match self.max_uses {
+            Some(max_uses) if max_uses > INVITE_MAX_USES => {
+                return Err(ValidationError::InviteMaxUsesTooHigh {
+                    max_uses,
+                    max: INVITE_MAX_USES,
+                });
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let mut invite = CreateInvite::new();
+        assert_eq!(json!({}), serde_json::to_value(&invite).unwrap());
+
+        invite.max_age(0).max_uses(5).temporary(true).unique(false);
+        assert_eq!(
+            json!({"max_age": 0, "max_uses": 5, "temporary": true, "unique": false}),
+            serde_json::to_value(&invite).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut invite = CreateInvite::new();
+        invite.max_age(INVITE_MAX_AGE).max_uses(INVITE_MAX_USES);
+        assert_eq!(invite.validate(), Ok(()));
+
+        invite.max_age(INVITE_MAX_AGE + 1);
+        assert_eq!(
+            invite.validate(),
+            Err(ValidationError::InviteMaxAgeTooLong {
+                max_age: INVITE_MAX_AGE + 1,
+                max: INVITE_MAX_AGE,
+            })
+        );
+
+        invite.max_age(0).max_uses(INVITE_MAX_USES + 1);
+        assert_eq!(
+            invite.validate(),
+            Err(ValidationError::InviteMaxUsesTooHigh {
+                max_uses: INVITE_MAX_USES + 1,
+                max: INVITE_MAX_USES,
+            })
+        );
+    }
+}
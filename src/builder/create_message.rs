@@ -0,0 +1,344 @@
+use serde::Serialize;
+
+use crate::constants::MESSAGE_MAX_LENGTH;
+use crate::internal::prelude::*;
+use crate::model::channel::message::embed::Embed;
+use crate::model::id::{RoleId, UserId};
+
+use super::embed::validate_embed;
+use super::ValidationError;
+
+/// A builder for the body of a message to be sent.
+///
+/// The contents of the message are validated by [`validate`] before the
+/// message is sent by the [`Http`] client.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::{AllowedMentions, CreateMessage};
+///
+/// let mut message = CreateMessage::new();
+/// message
+///     .content("Hello, world!")
+///     .tts(true)
+///     .allowed_mentions(AllowedMentions::none());
+///
+/// assert!(message.validate().is_ok());
+/// ```
+///
+/// [`validate`]: #method.validate
+/// [`Http`]: ../http/struct.Http.html
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embed: Option<Embed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+}
+
+impl CreateMessage {
+    /// Creates an empty message.
+    pub fn new() -> CreateMessage {
+        CreateMessage::default()
+    }
+
+    /// Sets the content of the message.
+    ///
+    /// The content can be at most 2000 Unicode code points long.
+    pub fn content<S: Into<String>>(&mut self, content: S) -> &mut Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Sets the nonce of the message, which is sent back in the message create
+    /// event and can be used to verify the message was sent.
+    pub fn nonce<S: Into<String>>(&mut self, nonce: S) -> &mut Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Sets whether the message is a text-to-speech message.
+    pub fn tts(&mut self, tts: bool) -> &mut Self {
+        self.tts = Some(tts);
+        self
+    }
+
+    /// Sets the embed of the message.
+    pub fn embed(&mut self, embed: Embed) -> &mut Self {
+        self.embed = Some(embed);
+        self
+    }
+
+    /// Sets the mentions that are allowed to notify users in the message.
+    pub fn allowed_mentions(&mut self, allowed_mentions: AllowedMentions) -> &mut Self {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Validates the message against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the message has neither content nor an
+    /// embed, if the content is longer than [`MESSAGE_MAX_LENGTH`] code points,
+    /// or if the embed exceeds any of the embed limits, such as
+    /// [`EMBED_MAX_LENGTH`] or [`EMBED_MAX_FIELDS`].
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`MESSAGE_MAX_LENGTH`]: ../constants/constant.MESSAGE_MAX_LENGTH.html
+    /// [`EMBED_MAX_LENGTH`]: ../constants/constant.EMBED_MAX_LENGTH.html
+    /// [`EMBED_MAX_FIELDS`]: ../constants/constant.EMBED_MAX_FIELDS.html
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        if self.is_empty() {
+            return Err(ValidationError::EmptyMessage);
+        }
+        self.validate_limits()
+    }
+
+    /// Validates the message, allowing it to be empty if files are attached.
+    pub(crate) fn validate_with_files(&self, has_files: bool) -> StdResult<(), ValidationError> {
+        if has_files {
+            self.validate_limits()
+        } else {
+            self.validate()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        let has_content = match &self.content {
+            Some(content) => !content.is_empty(),
+            None => false,
+        };
+        !has_content && self.embed.is_none()
+    }
+
+    fn validate_limits(&self) -> StdResult<(), ValidationError> {
+        if let Some(content) = &self.content {
+            validate_content(content)?;
+        }
+
+        if let Some(embed) = &self.embed {
+            validate_embed(embed)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Validates the length of the content of a message.
+pub(crate) fn validate_content(content: &str) -> StdResult<(), ValidationError> {
+    let length = content.chars().count();
+    if length > MESSAGE_MAX_LENGTH {
+        return Err(ValidationError::MessageTooLong {
+            length,
+            max: MESSAGE_MAX_LENGTH,
+        });
+    }
+    Ok(())
+}
+
+/// The types of mentions that can be parsed from the content of a message.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionType {
+    /// `@everyone` and `@here` mentions.
+    Everyone,
+    /// Role mentions.
+    Roles,
+    /// User mentions.
+    Users,
+}
+
+/// The mentions that are allowed to notify users in a message.
+///
+/// By default no mentions are allowed.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct AllowedMentions {
+    parse: Vec<MentionType>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    users: Vec<UserId>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<RoleId>,
+}
+
+impl AllowedMentions {
+    /// Creates an allowed mentions object that allows no mentions.
+    pub fn none() -> AllowedMentions {
+        AllowedMentions::default()
+    }
+
+    /// Allows all mentions of the given type parsed from the content.
+    pub fn parse(&mut self, kind: MentionType) -> &mut Self {
+        if !self.parse.contains(&kind) {
+            self.parse.push(kind);
+        }
+        self
+    }
+
+    /// Allows mentions of a specific user.
+    pub fn user(&mut self, user_id: UserId) -> &mut Self {
+        self.users.push(user_id);
+        self
+    }
+
+    /// Allows mentions of a specific role.
+    pub fn role(&mut self, role_id: RoleId) -> &mut Self {
+        self.roles.push(role_id);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use crate::constants::{EMBED_MAX_FIELDS, EMBED_MAX_LENGTH};
+
+    use super::*;
+
+    fn embed(mut value: serde_json::Value) -> Embed {
+        value["type"] = json!("rich");
+        Embed::deserialize(value).unwrap()
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut allowed_mentions = AllowedMentions::none();
+        allowed_mentions
+            .parse(MentionType::Roles)
+            .user(UserId::from(80351110224678912));
+
+        let mut message = CreateMessage::new();
+        message
+            .content("Hello, world!")
+            .nonce("1234")
+            .tts(false)
+            .embed(embed(json!({"title": "Title"})))
+            .allowed_mentions(allowed_mentions);
+
+        let value = json!({
+            "content": "Hello, world!",
+            "nonce": "1234",
+            "tts": false,
+            "embed": {
+                "type": "rich",
+                "title": "Title",
+                "color": 0
+            },
+            "allowed_mentions": {
+                "parse": ["roles"],
+                "users": ["80351110224678912"]
+            }
+        });
+        assert_eq!(value, serde_json::to_value(&message).unwrap());
+    }
+
+    #[test]
+    fn test_serialize_empty() {
+        let message = CreateMessage::new();
+        assert_eq!(json!({}), serde_json::to_value(&message).unwrap());
+
+        let mut message = CreateMessage::new();
+        message.allowed_mentions(AllowedMentions::none());
+        assert_eq!(
+            json!({"allowed_mentions": {"parse": []}}),
+            serde_json::to_value(&message).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate_empty() {
+        let mut message = CreateMessage::new();
+        assert_eq!(message.validate(), Err(ValidationError::EmptyMessage));
+
+        message.content("").tts(true);
+        assert_eq!(message.validate(), Err(ValidationError::EmptyMessage));
+
+        // A message with only files attached is not empty.
+        assert_eq!(message.validate_with_files(true), Ok(()));
+
+        message.embed(embed(json!({"title": "Title"})));
+        assert_eq!(message.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_content_length() {
+        let mut message = CreateMessage::new();
+
+        // Multi-byte characters count as a single code point.
+        message.content("é".repeat(MESSAGE_MAX_LENGTH));
+        assert_eq!(message.validate(), Ok(()));
+
+        message.content("a".repeat(MESSAGE_MAX_LENGTH + 1));
+        assert_eq!(
+            message.validate(),
+            Err(ValidationError::MessageTooLong {
+                length: MESSAGE_MAX_LENGTH + 1,
+                max: MESSAGE_MAX_LENGTH,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_embed_field_length() {
+        let mut message = CreateMessage::new();
+        message.embed(embed(json!({"title": "a".repeat(257)})));
+
+        assert_eq!(
+            message.validate(),
+            Err(ValidationError::EmbedFieldTooLong {
+                field: "title",
+                length: 257,
+                max: 256,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_embed_fields() {
+        let field = json!({"name": "name", "value": "value"});
+        let fields = vec![field; EMBED_MAX_FIELDS + 1];
+
+        let mut message = CreateMessage::new();
+        message.embed(embed(json!({ "fields": fields })));
+
+        assert_eq!(
+            message.validate(),
+            Err(ValidationError::TooManyEmbedFields {
+                count: EMBED_MAX_FIELDS + 1,
+                max: EMBED_MAX_FIELDS,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_embed_length() {
+        let mut message = CreateMessage::new();
+        message.embed(embed(json!({
+            "title": "a".repeat(256),
+            "description": "a".repeat(2048),
+            "fields": [
+                {"name": "a".repeat(256), "value": "a".repeat(1024)},
+                {"name": "a".repeat(256), "value": "a".repeat(1024)},
+            ],
+            "footer": {"text": "a".repeat(2048)},
+        })));
+
+        assert_eq!(
+            message.validate(),
+            Err(ValidationError::EmbedTooLong {
+                length: 256 + 2048 + 2 * (256 + 1024) + 2048,
+                max: EMBED_MAX_LENGTH,
+            })
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use serde::Serialize;
+
+use crate::constants::{
+    CHANNEL_NAME_MAX_LENGTH, CHANNEL_RATE_LIMIT_MAX, CHANNEL_TOPIC_MAX_LENGTH, VOICE_USER_LIMIT_MAX,
+};
+use crate::internal::prelude::*;
+use crate::model::channel::permissions::PermissionOverwrite;
+use crate::model::id::ChannelId;
+
+use super::error::{validate_text, validate_value};
+use super::ValidationError;
+
+/// A builder for the body of an edit to the settings of a channel.
+///
+/// Only the fields that are set are changed. Some settings only apply to text
+/// or voice channels.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::EditChannel;
+///
+/// let mut channel = EditChannel::new();
+/// channel.name("general").topic("General chat").rate_limit_per_user(5);
+///
+/// assert!(channel.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditChannel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit_per_user: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bitrate: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permission_overwrites: Option<Vec<PermissionOverwrite>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<Option<ChannelId>>,
+}
+
+impl EditChannel {
+    /// Creates an edit that leaves the channel unchanged.
+    pub fn new() -> EditChannel {
+        EditChannel::default()
+    }
+
+    /// Sets the name of the channel.
+    ///
+    /// The name can be at most 100 Unicode code points long.
+    pub fn name<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the position of the channel in the channel list.
+    pub fn position(&mut self, position: u64) -> &mut Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Sets the topic of a text channel.
+    ///
+    /// The topic can be at most 1024 Unicode code points long.
+    pub fn topic<S: Into<String>>(&mut self, topic: S) -> &mut Self {
+        self.topic = Some(topic.into());
+        self
+    }
+
+    /// Sets whether a text channel is NSFW.
+    pub fn nsfw(&mut self, nsfw: bool) -> &mut Self {
+        self.nsfw = Some(nsfw);
+        self
+    }
+
+    /// Sets the number of seconds users have to wait between sending messages
+    /// in a text channel, `0` for no limit.
+    ///
+    /// The limit can be at most 21600 seconds (6 hours).
+    pub fn rate_limit_per_user(&mut self, seconds: u64) -> &mut Self {
+        self.rate_limit_per_user = Some(seconds);
+        self
+    }
+
+    /// Sets the bitrate of a voice channel in bits per second.
+    pub fn bitrate(&mut self, bitrate: u64) -> &mut Self {
+        self.bitrate = Some(bitrate);
+        self
+    }
+
+    /// Sets the maximum number of users in a voice channel, `0` for no limit.
+    ///
+    /// The limit can be at most 99 users.
+    pub fn user_limit(&mut self, user_limit: u64) -> &mut Self {
+        self.user_limit = Some(user_limit);
+        self
+    }
+
+    /// Sets the permission overwrites of the channel, replacing the existing
+    /// overwrites.
+    pub fn permission_overwrites<I>(&mut self, overwrites: I) -> &mut Self
+    where
+        I: IntoIterator<Item = PermissionOverwrite>,
+    {
+        self.permission_overwrites = Some(overwrites.into_iter().collect());
+        self
+    }
+
+    /// Sets the category the channel is in, `None` to remove it from its
+    /// category.
+    pub fn parent_id(&mut self, parent_id: Option<ChannelId>) -> &mut Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+
+    /// Validates the edit against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the name is longer than
+    /// [`CHANNEL_NAME_MAX_LENGTH`] code points, the topic is longer than
+    /// [`CHANNEL_TOPIC_MAX_LENGTH`] code points, the rate limit per user is
+    /// greater than [`CHANNEL_RATE_LIMIT_MAX`], or the user limit is greater
+    /// than [`VOICE_USER_LIMIT_MAX`].
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`CHANNEL_NAME_MAX_LENGTH`]: ../constants/constant.CHANNEL_NAME_MAX_LENGTH.html
+    /// [`CHANNEL_TOPIC_MAX_LENGTH`]: ../constants/constant.CHANNEL_TOPIC_MAX_LENGTH.html
+    /// [`CHANNEL_RATE_LIMIT_MAX`]: ../constants/constant.CHANNEL_RATE_LIMIT_MAX.html
+    /// [`VOICE_USER_LIMIT_MAX`]: ../constants/constant.VOICE_USER_LIMIT_MAX.html
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        if let Some(name) = &self.name {
+            validate_text("channel name", name, CHANNEL_NAME_MAX_LENGTH)?;
+        }
+
+        if let Some(topic) = &self.topic {
+            validate_text("channel topic", topic, CHANNEL_TOPIC_MAX_LENGTH)?;
+        }
+
+        if let Some(seconds) = self.rate_limit_per_user {
+            validate_value("rate limit per user", seconds, CHANNEL_RATE_LIMIT_MAX)?;
+        }
+
+        if let Some(user_limit) = self.user_limit {
+            validate_value("user limit", user_limit, VOICE_USER_LIMIT_MAX)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::model::id::RoleId;
+    use crate::model::permissions::Permissions;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let channel = EditChannel::new();
+        assert_eq!(json!({}), serde_json::to_value(&channel).unwrap());
+
+        let mut channel = EditChannel::new();
+        channel
+            .name("general")
+            .topic("General chat")
+            .nsfw(false)
+            .permission_overwrites(vec![PermissionOverwrite::new(
+                RoleId::from(1234),
+                Permissions::SEND_MESSAGES,
+                Permissions::empty(),
+            )])
+            .parent_id(None);
+
+        let value = json!({
+            "name": "general",
+            "topic": "General chat",
+            "nsfw": false,
+            "permission_overwrites": [
+                {"id": "1234", "type": "role", "allow": 0x800, "deny": 0}
+            ],
+            "parent_id": null
+        });
+        assert_eq!(value, serde_json::to_value(&channel).unwrap());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut channel = EditChannel::new();
+        channel
+            .name("a".repeat(CHANNEL_NAME_MAX_LENGTH))
+            .topic("a".repeat(CHANNEL_TOPIC_MAX_LENGTH))
+            .rate_limit_per_user(CHANNEL_RATE_LIMIT_MAX)
+            .user_limit(VOICE_USER_LIMIT_MAX);
+        assert_eq!(channel.validate(), Ok(()));
+
+        channel.topic("a".repeat(CHANNEL_TOPIC_MAX_LENGTH + 1));
+        assert_eq!(
+            channel.validate(),
+            Err(ValidationError::TextTooLong {
+                field: "channel topic",
+                length: CHANNEL_TOPIC_MAX_LENGTH + 1,
+                max: CHANNEL_TOPIC_MAX_LENGTH,
+            })
+        );
+
+        channel.topic("").user_limit(VOICE_USER_LIMIT_MAX + 1);
+        assert_eq!(
+            channel.validate(),
+            Err(ValidationError::ValueTooHigh {
+                field: "user limit",
+                value: VOICE_USER_LIMIT_MAX + 1,
+                max: VOICE_USER_LIMIT_MAX,
+            })
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use serde::Serialize;
+
+use crate::constants::NICKNAME_MAX_LENGTH;
+use crate::internal::prelude::*;
+use crate::model::id::{ChannelId, RoleId};
+
+use super::error::validate_text;
+use super::ValidationError;
+
+/// A builder for the body of an edit to a member of a guild.
+///
+/// Only the fields that are set are changed.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::EditMember;
+/// use strife::model::id::RoleId;
+///
+/// let mut member = EditMember::new();
+/// member.nick("Nelly").roles(vec![RoleId::from(1234)]).mute(true);
+///
+/// assert!(member.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditMember {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nick: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<RoleId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deaf: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_id: Option<Option<ChannelId>>,
+}
+
+impl EditMember {
+    /// Creates an edit that leaves the member unchanged.
+    pub fn new() -> EditMember {
+        EditMember::default()
+    }
+
+    /// Sets the nickname of the member, an empty nickname removes it.
+    ///
+    /// The nickname can be at most 32 Unicode code points long.
+    pub fn nick<S: Into<String>>(&mut self, nick: S) -> &mut Self {
+        self.nick = Some(nick.into());
+        self
+    }
+
+    /// Sets the roles of the member, replacing all of their existing roles.
+    pub fn roles<I>(&mut self, roles: I) -> &mut Self
+    where
+        I: IntoIterator<Item = RoleId>,
+    {
+        self.roles = Some(roles.into_iter().collect());
+        self
+    }
+
+    /// Sets whether the member is muted in voice channels.
+    pub fn mute(&mut self, mute: bool) -> &mut Self {
+        self.mute = Some(mute);
+        self
+    }
+
+    /// Sets whether the member is deafened in voice channels.
+    pub fn deaf(&mut self, deaf: bool) -> &mut Self {
+        self.deaf = Some(deaf);
+        self
+    }
+
+    /// Moves the member to a voice channel, or disconnects them from voice if
+    /// `None`.
+    ///
+    /// This only applies to members connected to voice.
+    pub fn channel_id(&mut self, channel_id: Option<ChannelId>) -> &mut Self {
+        self.channel_id = Some(channel_id);
+        self
+    }
+
+    /// Validates the edit against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the nickname is longer than
+    /// [`NICKNAME_MAX_LENGTH`] code points.
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`NICKNAME_MAX_LENGTH`]: ../constants/constant.NICKNAME_MAX_LENGTH.html
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        if let Some(nick) = &self.nick {
+            validate_text("nickname", nick, NICKNAME_MAX_LENGTH)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let member = EditMember::new();
+        assert_eq!(json!({}), serde_json::to_value(&member).unwrap());
+
+        let mut member = EditMember::new();
+        member
+            .nick("")
+            .roles(vec![RoleId::from(1234)])
+            .deaf(false)
+            .channel_id(None);
+
+        let value = json!({
+            "nick": "",
+            "roles": ["1234"],
+            "deaf": false,
+            "channel_id": null
+        });
+        assert_eq!(value, serde_json::to_value(&member).unwrap());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut member = EditMember::new();
+        member.nick("a".repeat(NICKNAME_MAX_LENGTH));
+        assert_eq!(member.validate(), Ok(()));
+
+        member.nick("a".repeat(NICKNAME_MAX_LENGTH + 1));
+        assert_eq!(
+            member.validate(),
+            Err(ValidationError::TextTooLong {
+                field: "nickname",
+                length: NICKNAME_MAX_LENGTH + 1,
+                max: NICKNAME_MAX_LENGTH,
+            })
+        );
+    }
+}
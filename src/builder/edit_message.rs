@@ -0,0 +1,130 @@
+use serde::Serialize;
+
+use crate::internal::prelude::*;
+use crate::model::channel::message::embed::Embed;
+
+use super::create_message::{validate_content, AllowedMentions};
+use super::embed::validate_embed;
+use super::ValidationError;
+
+/// A builder for the body of an edit to a message.
+///
+/// Only the fields that are set are changed, the rest of the message is left
+/// as it is.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::EditMessage;
+///
+/// let mut message = EditMessage::new();
+/// message.content("Hello, world! (edited)");
+///
+/// assert!(message.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embed: Option<Embed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+}
+
+impl EditMessage {
+    /// Creates an edit that leaves the message unchanged.
+    pub fn new() -> EditMessage {
+        EditMessage::default()
+    }
+
+    /// Sets the content of the message.
+    ///
+    /// The content can be at most 2000 Unicode code points long.
+    pub fn content<S: Into<String>>(&mut self, content: S) -> &mut Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Sets the embed of the message.
+    pub fn embed(&mut self, embed: Embed) -> &mut Self {
+        self.embed = Some(embed);
+        self
+    }
+
+    /// Sets the mentions that are allowed to notify users in the message.
+    pub fn allowed_mentions(&mut self, allowed_mentions: AllowedMentions) -> &mut Self {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Validates the edit against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the content is longer than
+    /// [`MESSAGE_MAX_LENGTH`] code points, or if the embed exceeds any of the
+    /// embed limits.
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`MESSAGE_MAX_LENGTH`]: ../constants/constant.MESSAGE_MAX_LENGTH.html
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        if let Some(content) = &self.content {
+            validate_content(content)?;
+        }
+
+        if let Some(embed) = &self.embed {
+            validate_embed(embed)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::builder::EmbedBuilder;
+    use crate::constants::MESSAGE_MAX_LENGTH;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let message = EditMessage::new();
+        assert_eq!(json!({}), serde_json::to_value(&message).unwrap());
+
+        let mut message = EditMessage::new();
+        message
+            .content("Hello, world!")
+            .embed(EmbedBuilder::new().title("Title").build().unwrap())
+            .allowed_mentions(AllowedMentions::none());
+
+        let value = json!({
+            "content": "Hello, world!",
+            "embed": {
+                "type": "rich",
+                "title": "Title",
+                "color": 0
+            },
+            "allowed_mentions": {"parse": []}
+        });
+        assert_eq!(value, serde_json::to_value(&message).unwrap());
+    }
+
+    #[test]
+    fn test_validate_content_length() {
+        let mut message = EditMessage::new();
+        assert_eq!(message.validate(), Ok(()));
+
+        message.content("a".repeat(MESSAGE_MAX_LENGTH + 1));
+        assert_eq!(
+            message.validate(),
+            Err(ValidationError::MessageTooLong {
+                length: MESSAGE_MAX_LENGTH + 1,
+                max: MESSAGE_MAX_LENGTH,
+            })
+        );
+    }
+}
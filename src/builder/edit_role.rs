@@ -0,0 +1,141 @@
+use serde::Serialize;
+
+use crate::constants::ROLE_NAME_MAX_LENGTH;
+use crate::internal::prelude::*;
+use crate::model::color::Color;
+use crate::model::permissions::Permissions;
+
+use super::error::validate_text;
+use super::ValidationError;
+
+/// A builder for the body of a role to be created, or an edit to a role.
+///
+/// Only the fields that are set are changed. Fields that are not set when
+/// creating a role take the defaults of a new role.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::EditRole;
+/// use strife::model::permissions::Permissions;
+///
+/// let mut role = EditRole::new();
+/// role.name("Moderator")
+///     .permissions(Permissions::KICK_MEMBERS | Permissions::BAN_MEMBERS)
+///     .hoist(true);
+///
+/// assert!(role.validate().is_ok());
+/// ```
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditRole {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionable: Option<bool>,
+}
+
+impl EditRole {
+    /// Creates an edit that leaves the role unchanged.
+    pub fn new() -> EditRole {
+        EditRole::default()
+    }
+
+    /// Sets the name of the role.
+    ///
+    /// The name can be at most 100 Unicode code points long.
+    pub fn name<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the permissions of members with the role.
+    pub fn permissions(&mut self, permissions: Permissions) -> &mut Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Sets the color of the role.
+    pub fn color<C: Into<Color>>(&mut self, color: C) -> &mut Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Sets whether members with the role are listed separately in the member
+    /// list.
+    pub fn hoist(&mut self, hoist: bool) -> &mut Self {
+        self.hoist = Some(hoist);
+        self
+    }
+
+    /// Sets whether the role can be mentioned by everyone.
+    pub fn mentionable(&mut self, mentionable: bool) -> &mut Self {
+        self.mentionable = Some(mentionable);
+        self
+    }
+
+    /// Validates the role against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the name is longer than
+    /// [`ROLE_NAME_MAX_LENGTH`] code points.
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`ROLE_NAME_MAX_LENGTH`]: ../constants/constant.ROLE_NAME_MAX_LENGTH.html
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        if let Some(name) = &self.name {
+            validate_text("role name", name, ROLE_NAME_MAX_LENGTH)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let role = EditRole::new();
+        assert_eq!(json!({}), serde_json::to_value(&role).unwrap());
+
+        let mut role = EditRole::new();
+        role.name("Moderator")
+            .permissions(Permissions::KICK_MEMBERS)
+            .color(0x00ff_0000)
+            .mentionable(false);
+
+        let value = json!({
+            "name": "Moderator",
+            "permissions": 2,
+            "color": 0x00ff_0000,
+            "mentionable": false
+        });
+        assert_eq!(value, serde_json::to_value(&role).unwrap());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut role = EditRole::new();
+        role.name("a".repeat(ROLE_NAME_MAX_LENGTH));
+        assert_eq!(role.validate(), Ok(()));
+
+        role.name("a".repeat(ROLE_NAME_MAX_LENGTH + 1));
+        assert_eq!(
+            role.validate(),
+            Err(ValidationError::TextTooLong {
+                field: "role name",
+                length: ROLE_NAME_MAX_LENGTH + 1,
+                max: ROLE_NAME_MAX_LENGTH,
+            })
+        );
+    }
+}
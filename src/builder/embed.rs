@@ -0,0 +1,355 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::constants::{
+    EMBED_AUTHOR_NAME_MAX_LENGTH, EMBED_DESCRIPTION_MAX_LENGTH, EMBED_FIELD_NAME_MAX_LENGTH,
+    EMBED_FIELD_VALUE_MAX_LENGTH, EMBED_FOOTER_TEXT_MAX_LENGTH, EMBED_MAX_FIELDS, EMBED_MAX_LENGTH,
+    EMBED_TITLE_MAX_LENGTH,
+};
+use crate::internal::prelude::*;
+use crate::model::channel::message::embed::{
+    Embed, EmbedAuthor, EmbedField, EmbedFooter, EmbedImage, EmbedThumbnail, EmbedType,
+};
+use crate::model::color::Color;
+
+use super::ValidationError;
+
+/// A builder for a rich [`Embed`].
+///
+/// The embed is validated against the limits imposed by Discord when it is
+/// built.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::EmbedBuilder;
+/// use strife::model::channel::message::embed::EmbedFooter;
+///
+/// let embed = EmbedBuilder::new()
+///     .title("Report")
+///     .description("All systems operational.")
+///     .color(0x00FF00)
+///     .field("Uptime", "99.9%")
+///     .inline_field("Errors", "0")
+///     .footer(EmbedFooter::new("Generated daily"))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(embed.fields.len(), 2);
+/// ```
+///
+/// [`Embed`]: ../model/channel/message/embed/struct.Embed.html
+#[derive(Clone, Debug)]
+pub struct EmbedBuilder {
+    embed: Embed,
+}
+
+impl Default for EmbedBuilder {
+    fn default() -> Self {
+        EmbedBuilder::new()
+    }
+}
+
+impl EmbedBuilder {
+    /// Creates an empty embed builder.
+    pub fn new() -> EmbedBuilder {
+        EmbedBuilder {
+            embed: Embed {
+                title: None,
+                kind: EmbedType::Rich,
+                description: None,
+                url: None,
+                timestamp: None,
+                color: Color::default(),
+                footer: None,
+                image: None,
+                thumbnail: None,
+                video: None,
+                provider: None,
+                author: None,
+                fields: Vec::new(),
+            },
+        }
+    }
+
+    /// Sets the title of the embed.
+    ///
+    /// The title can be at most 256 Unicode code points long.
+    pub fn title<S: Into<String>>(&mut self, title: S) -> &mut Self {
+        self.embed.title = Some(title.into());
+        self
+    }
+
+    /// Sets the description of the embed.
+    ///
+    /// The description can be at most 2048 Unicode code points long.
+    pub fn description<S: Into<String>>(&mut self, description: S) -> &mut Self {
+        self.embed.description = Some(description.into());
+        self
+    }
+
+    /// Sets the URL the title of the embed links to.
+    pub fn url<S: Into<String>>(&mut self, url: S) -> &mut Self {
+        self.embed.url = Some(url.into());
+        self
+    }
+
+    /// Sets the timestamp displayed in the footer of the embed.
+    pub fn timestamp<T>(&mut self, timestamp: T) -> &mut Self
+    where
+        T: Into<DateTime<FixedOffset>>,
+    {
+        self.embed.timestamp = Some(timestamp.into());
+        self
+    }
+
+    /// Sets the color of the embed.
+    pub fn color<C: Into<Color>>(&mut self, color: C) -> &mut Self {
+        self.embed.color = color.into();
+        self
+    }
+
+    /// Sets the footer of the embed.
+    pub fn footer(&mut self, footer: EmbedFooter) -> &mut Self {
+        self.embed.footer = Some(footer);
+        self
+    }
+
+    /// Sets the URL of the image of the embed.
+    pub fn image<S: Into<String>>(&mut self, url: S) -> &mut Self {
+        self.embed.image = Some(EmbedImage::new(url));
+        self
+    }
+
+    /// Sets the URL of the thumbnail of the embed.
+    pub fn thumbnail<S: Into<String>>(&mut self, url: S) -> &mut Self {
+        self.embed.thumbnail = Some(EmbedThumbnail::new(url));
+        self
+    }
+
+    /// Sets the author of the embed.
+    pub fn author(&mut self, author: EmbedAuthor) -> &mut Self {
+        self.embed.author = Some(author);
+        self
+    }
+
+    /// Adds a field to the embed, that is not displayed inline.
+    ///
+    /// An embed can have at most 25 fields, and the value of a field can be at
+    /// most 1024 Unicode code points long.
+    pub fn field<N, V>(&mut self, name: N, value: V) -> &mut Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.embed.fields.push(EmbedField::new(name, value, false));
+        self
+    }
+
+    /// Adds a field to the embed, that is displayed inline.
+    ///
+    /// An embed can have at most 25 fields, and the value of a field can be at
+    /// most 1024 Unicode code points long.
+    pub fn inline_field<N, V>(&mut self, name: N, value: V) -> &mut Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.embed.fields.push(EmbedField::new(name, value, true));
+        self
+    }
+
+    /// Builds the embed, validating it against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if any text in the embed is too long, the
+    /// total text in the embed is longer than [`EMBED_MAX_LENGTH`], or there
+    /// are more than [`EMBED_MAX_FIELDS`] fields.
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`EMBED_MAX_LENGTH`]: ../constants/constant.EMBED_MAX_LENGTH.html
+    /// [`EMBED_MAX_FIELDS`]: ../constants/constant.EMBED_MAX_FIELDS.html
+    pub fn build(&self) -> StdResult<Embed, ValidationError> {
+        validate_embed(&self.embed)?;
+        Ok(self.embed.clone())
+    }
+}
+
+/// Validates the text of an embed against the limits imposed by Discord.
+///
+/// The length of each text field is checked, as well as the total length of
+/// all text in the embed and the number of fields.
+pub(crate) fn validate_embed(embed: &Embed) -> StdResult<(), ValidationError> {
+    if embed.fields.len() > EMBED_MAX_FIELDS {
+        return Err(ValidationError::TooManyEmbedFields {
+            count: embed.fields.len(),
+            max: EMBED_MAX_FIELDS,
+        });
+    }
+
+    let mut total = 0;
+    let mut check = |field, text: &str, max| {
+        let length = text.chars().count();
+        total += length;
+
+        if length > max {
+            Err(ValidationError::EmbedFieldTooLong { field, length, max })
+        } else {
+            Ok(())
+        }
+    };
+
+    if let Some(title) = &embed.title {
+        check("title", title, EMBED_TITLE_MAX_LENGTH)?;
+    }
+    if let Some(description) = &embed.description {
+        check("description", description, EMBED_DESCRIPTION_MAX_LENGTH)?;
+    }
+    for field in &embed.fields {
+        check("field name", &field.name, EMBED_FIELD_NAME_MAX_LENGTH)?;
+        check("field value", &field.value, EMBED_FIELD_VALUE_MAX_LENGTH)?;
+    }
+    if let Some(footer) = &embed.footer {
+        check("footer text", &footer.text, EMBED_FOOTER_TEXT_MAX_LENGTH)?;
+    }
+    if let Some(author) = &embed.author {
+        check("author name", &author.name, EMBED_AUTHOR_NAME_MAX_LENGTH)?;
+    }
+
+    if total > EMBED_MAX_LENGTH {
+        return Err(ValidationError::EmbedTooLong {
+            length: total,
+            max: EMBED_MAX_LENGTH,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let timestamp = DateTime::parse_from_rfc3339("2020-01-05T16:02:04.179+00:00").unwrap();
+        let embed = EmbedBuilder::new()
+            .title("Title")
+            .description("Description")
+            .url("https://example.com")
+            .timestamp(timestamp)
+            .color(0x123456)
+            .footer(EmbedFooter::new("Footer").icon_url("https://example.com/footer.png"))
+            .image("https://example.com/image.png")
+            .thumbnail("https://example.com/thumbnail.png")
+            .author(EmbedAuthor::new("Author").url("https://example.com/author"))
+            .field("Field", "Value")
+            .inline_field("Inline", "Value")
+            .build()
+            .unwrap();
+
+        let value = json!({
+            "type": "rich",
+            "title": "Title",
+            "description": "Description",
+            "url": "https://example.com",
+            "color": 0x123456,
+            "footer": {
+                "text": "Footer",
+                "icon_url": "https://example.com/footer.png"
+            },
+            "image": {"url": "https://example.com/image.png"},
+            "thumbnail": {"url": "https://example.com/thumbnail.png"},
+            "author": {
+                "name": "Author",
+                "url": "https://example.com/author"
+            },
+            "fields": [
+                {"name": "Field", "value": "Value"},
+                {"name": "Inline", "value": "Value", "inline": true}
+            ]
+        });
+        let mut serialized = serde_json::to_value(&embed).unwrap();
+        // The serialized format of timestamps depends on the version of chrono.
+        let serialized_timestamp = serialized.as_object_mut().unwrap().remove("timestamp");
+        let serialized_timestamp: DateTime<FixedOffset> =
+            serde_json::from_value(serialized_timestamp.unwrap()).unwrap();
+
+        assert_eq!(value, serialized);
+        assert_eq!(Some(timestamp), embed.timestamp);
+        assert_eq!(timestamp, serialized_timestamp);
+    }
+
+    #[test]
+    fn test_title_too_long() {
+        let err = EmbedBuilder::new()
+            .title("a".repeat(EMBED_TITLE_MAX_LENGTH + 1))
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            ValidationError::EmbedFieldTooLong {
+                field: "title",
+                length: EMBED_TITLE_MAX_LENGTH + 1,
+                max: EMBED_TITLE_MAX_LENGTH,
+            }
+        );
+    }
+
+    #[test]
+    fn test_field_value_too_long() {
+        let err = EmbedBuilder::new()
+            .field("Field", "a".repeat(EMBED_FIELD_VALUE_MAX_LENGTH + 1))
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            ValidationError::EmbedFieldTooLong {
+                field: "field value",
+                length: EMBED_FIELD_VALUE_MAX_LENGTH + 1,
+                max: EMBED_FIELD_VALUE_MAX_LENGTH,
+            }
+        );
+    }
+
+    #[test]
+    fn test_too_many_fields() {
+        let mut builder = EmbedBuilder::new();
+        for i in 0..EMBED_MAX_FIELDS {
+            builder.inline_field(i.to_string(), "Value");
+        }
+        assert!(builder.build().is_ok());
+
+        builder.inline_field("Extra", "Value");
+        assert_eq!(
+            builder.build().unwrap_err(),
+            ValidationError::TooManyEmbedFields {
+                count: EMBED_MAX_FIELDS + 1,
+                max: EMBED_MAX_FIELDS,
+            }
+        );
+    }
+
+    #[test]
+    fn test_total_too_long() {
+        let mut builder = EmbedBuilder::new();
+        for _ in 0..6 {
+            builder.field(
+                "a".repeat(EMBED_FIELD_NAME_MAX_LENGTH),
+                "a".repeat(EMBED_FIELD_VALUE_MAX_LENGTH),
+            );
+        }
+
+        assert_eq!(
+            builder.build().unwrap_err(),
+            ValidationError::EmbedTooLong {
+                length: 6 * (EMBED_FIELD_NAME_MAX_LENGTH + EMBED_FIELD_VALUE_MAX_LENGTH),
+                max: EMBED_MAX_LENGTH,
+            }
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use thiserror::Error;
+
+/// An error validating the contents of a builder.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum ValidationError {
+    /// A message has no content, embed or files.
+    #[error("message has no content, embed or files")]
+    EmptyMessage,
+    /// The content of a message is too long.
+    #[error("message content is {length} characters long, the maximum is {max}")]
+    MessageTooLong {
+        /// The length of the content in Unicode code points.
+        length: usize,
+        /// The maximum length allowed.
+        max: usize,
+    },
+    /// The total text of an embed is too long.
+    #[error("embed is {length} characters long, the maximum is {max}")]
+    EmbedTooLong {
+        /// The total length of the embed text in Unicode code points.
+        length: usize,
+        /// The maximum length allowed.
+        max: usize,
+    },
+    /// A text field of an embed is too long.
+    #[error("embed {field} is {length} characters long, the maximum is {max}")]
+    EmbedFieldTooLong {
+        /// The name of the embed field.
+        field: &'static str,
+        /// The length of the field in Unicode code points.
+        length: usize,
+        /// The maximum length allowed.
+        max: usize,
+    },
+    /// A message has too many embeds.
+    #[error("message has {count} embeds, the maximum is {max}")]
+    TooManyEmbeds {
+        /// The number of embeds in the message.
+        count: usize,
+        /// The maximum number of embeds allowed.
+        max: usize,
+    },
+    /// The maximum age of an invite is too long.
+    #[error("invite max age is {max_age} seconds, the maximum is {max}")]
+    InviteMaxAgeTooLong {
+        /// The maximum age of the invite in seconds.
+        max_age: u64,
+        /// The longest maximum age allowed.
+        max: u64,
+    },
+    /// The maximum number of uses of an invite is too high.
+    #[error("invite max uses is {max_uses}, the maximum is {max}")]
+    InviteMaxUsesTooHigh {
+        /// The maximum number of uses of the invite.
+        max_uses: u64,
+        /// The highest maximum number of uses allowed.
+        max: u64,
+    },
+    /// An audit log reason is too long.
+    #[error("audit log reason is {length} characters long, the maximum is {max}")]
+    AuditLogReasonTooLong {
+        /// The length of the reason in Unicode code points.
+        length: usize,
+        /// The maximum length allowed.
+        max: usize,
+    },
+    /// A text field of a request body is too long.
+    #[error("{field} is {length} characters long, the maximum is {max}")]
+    TextTooLong {
+        /// The name of the field.
+        field: &'static str,
+        /// The length of the text in Unicode code points.
+        length: usize,
+        /// The maximum length allowed.
+        max: usize,
+    },
+    /// A numeric field of a request body is too high.
+    #[error("{field} is {value}, the maximum is {max}")]
+    ValueTooHigh {
+        /// The name of the field.
+        field: &'static str,
+        /// The value of the field.
+        value: u64,
+        /// The maximum value allowed.
+        max: u64,
+    },
+    /// An embed has too many fields.
+    #[error("embed has {count} fields, the maximum is {max}")]
+    TooManyEmbedFields {
+        /// The number of fields in the embed.
+        count: usize,
+        /// The maximum number of fields allowed.
+        max: usize,
+    },
+}
+
+/// Validates the length of a text field of a request body.
+pub(crate) fn validate_text(
+    field: &'static str,
+    text: &str,
+    max: usize,
+) -> Result<(), ValidationError> {
+    let length = text.chars().count();
+    if length > max {
+        return Err(ValidationError::TextTooLong { field, length, max });
+    }
+    Ok(())
+}
+
+/// Validates a numeric field of a request body is at most `max`.
+pub(crate) fn validate_value(
+    field: &'static str,
+    value: u64,
+    max: u64,
+) -> Result<(), ValidationError> {
+    if value > max {
+        return Err(ValidationError::ValueTooHigh { field, value, max });
+    }
+    Ok(())
+}
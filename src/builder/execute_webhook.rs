@@ -0,0 +1,231 @@
+use serde::Serialize;
+
+use crate::constants::WEBHOOK_MAX_EMBEDS;
+use crate::internal::prelude::*;
+use crate::model::channel::message::embed::Embed;
+
+use super::create_message::{validate_content, AllowedMentions};
+use super::embed::validate_embed;
+use super::ValidationError;
+
+/// A builder for the body of a message to be sent by a webhook.
+///
+/// The contents of the message are validated by [`validate`] before the
+/// message is sent.
+///
+/// # Examples
+///
+/// ```
+/// use strife::builder::{EmbedBuilder, ExecuteWebhook};
+///
+/// let embed = EmbedBuilder::new().title("Build passed").build().unwrap();
+///
+/// let mut message = ExecuteWebhook::new();
+/// message
+///     .username("CI")
+///     .avatar_url("https://example.com/ci.png")
+///     .embed(embed);
+///
+/// assert!(message.validate().is_ok());
+/// ```
+///
+/// [`validate`]: #method.validate
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ExecuteWebhook {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tts: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<Embed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+}
+
+impl ExecuteWebhook {
+    /// Creates an empty message.
+    pub fn new() -> ExecuteWebhook {
+        ExecuteWebhook::default()
+    }
+
+    /// Sets the content of the message.
+    ///
+    /// The content can be at most 2000 Unicode code points long.
+    pub fn content<S: Into<String>>(&mut self, content: S) -> &mut Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Overrides the default username of the webhook.
+    pub fn username<S: Into<String>>(&mut self, username: S) -> &mut Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Overrides the default avatar of the webhook.
+    pub fn avatar_url<S: Into<String>>(&mut self, avatar_url: S) -> &mut Self {
+        self.avatar_url = Some(avatar_url.into());
+        self
+    }
+
+    /// Sets whether the message is a text-to-speech message.
+    pub fn tts(&mut self, tts: bool) -> &mut Self {
+        self.tts = Some(tts);
+        self
+    }
+
+    /// Adds an embed to the message.
+    ///
+    /// A message sent by a webhook can have at most 10 embeds.
+    pub fn embed(&mut self, embed: Embed) -> &mut Self {
+        self.embeds.push(embed);
+        self
+    }
+
+    /// Sets the mentions that are allowed to notify users in the message.
+    pub fn allowed_mentions(&mut self, allowed_mentions: AllowedMentions) -> &mut Self {
+        self.allowed_mentions = Some(allowed_mentions);
+        self
+    }
+
+    /// Validates the message against the limits imposed by Discord.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the message has neither content nor
+    /// embeds, if the content is longer than [`MESSAGE_MAX_LENGTH`] code
+    /// points, if there are more than [`WEBHOOK_MAX_EMBEDS`] embeds, or if any
+    /// embed exceeds the embed limits.
+    ///
+    /// [`ValidationError`]: enum.ValidationError.html
+    /// [`MESSAGE_MAX_LENGTH`]: ../constants/constant.MESSAGE_MAX_LENGTH.html
+    /// [`WEBHOOK_MAX_EMBEDS`]: ../constants/constant.WEBHOOK_MAX_EMBEDS.html
+    pub fn validate(&self) -> StdResult<(), ValidationError> {
+        if self.is_empty() {
+            return Err(ValidationError::EmptyMessage);
+        }
+        self.validate_limits()
+    }
+
+    /// Validates the message, allowing it to be empty if files are attached.
+    pub(crate) fn validate_with_files(&self, has_files: bool) -> StdResult<(), ValidationError> {
+        if has_files {
+            self.validate_limits()
+        } else {
+            self.validate()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        let has_content = match &self.content {
+            Some(content) => !content.is_empty(),
+            None => false,
+        };
+        !has_content && self.embeds.is_empty()
+    }
+
+    fn validate_limits(&self) -> StdResult<(), ValidationError> {
+        if let Some(content) = &self.content {
+            validate_content(content)?;
+        }
+
+        if self.embeds.len() > WEBHOOK_MAX_EMBEDS {
+            return Err(ValidationError::TooManyEmbeds {
+                count: self.embeds.len(),
+                max: WEBHOOK_MAX_EMBEDS,
+            });
+        }
+
+        for embed in &self.embeds {
+            validate_embed(embed)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::builder::EmbedBuilder;
+    use crate::constants::MESSAGE_MAX_LENGTH;
+
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let mut message = ExecuteWebhook::new();
+        message
+            .content("Hello, world!")
+            .username("CI")
+            .avatar_url("https://example.com/ci.png")
+            .embed(EmbedBuilder::new().title("Title").build().unwrap())
+            .allowed_mentions(AllowedMentions::none());
+
+        let value = json!({
+            "content": "Hello, world!",
+            "username": "CI",
+            "avatar_url": "https://example.com/ci.png",
+            "embeds": [{
+                "type": "rich",
+                "title": "Title",
+                "color": 0
+            }],
+            "allowed_mentions": {"parse": []}
+        });
+        assert_eq!(value, serde_json::to_value(&message).unwrap());
+    }
+
+    #[test]
+    fn test_validate_embeds() {
+        let embed = EmbedBuilder::new().title("Title").build().unwrap();
+
+        let mut message = ExecuteWebhook::new();
+        for _ in 0..WEBHOOK_MAX_EMBEDS {
+            message.embed(embed.clone());
+        }
+        assert_eq!(message.validate(), Ok(()));
+
+        message.embed(embed);
+        assert_eq!(
+            message.validate(),
+            Err(ValidationError::TooManyEmbeds {
+                count: WEBHOOK_MAX_EMBEDS + 1,
+                max: WEBHOOK_MAX_EMBEDS,
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_empty() {
+        let mut message = ExecuteWebhook::new();
+        assert_eq!(message.validate(), Err(ValidationError::EmptyMessage));
+
+        message.content("").username("CI");
+        assert_eq!(message.validate(), Err(ValidationError::EmptyMessage));
+
+        // A message with only files attached is not empty.
+        assert_eq!(message.validate_with_files(true), Ok(()));
+
+        message.content("Hello, world!");
+        assert_eq!(message.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_content_length() {
+        let mut message = ExecuteWebhook::new();
+        message.content("a".repeat(MESSAGE_MAX_LENGTH + 1));
+        assert_eq!(
+            message.validate_with_files(true),
+            Err(ValidationError::MessageTooLong {
+                length: MESSAGE_MAX_LENGTH + 1,
+                max: MESSAGE_MAX_LENGTH,
+            })
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Builders for the bodies of requests to the Discord API.
+//!
+//! Builders validate their contents against the limits imposed by Discord
+//! before a request is sent, returning a [`ValidationError`] instead of letting
+//! Discord reject the request.
+//!
+//! [`ValidationError`]: enum.ValidationError.html
+
+mod create_invite;
+mod create_message;
+mod edit_channel;
+mod edit_member;
+mod edit_message;
+mod edit_role;
+mod embed;
+mod error;
+mod execute_webhook;
+
+pub use self::create_invite::CreateInvite;
+pub use self::create_message::{AllowedMentions, CreateMessage, MentionType};
+pub use self::edit_channel::EditChannel;
+pub use self::edit_member::EditMember;
+pub use self::edit_message::EditMessage;
+pub use self::edit_role::EditRole;
+pub use self::embed::EmbedBuilder;
+pub use self::error::ValidationError;
+pub use self::execute_webhook::ExecuteWebhook;
@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::http::Http;
+
+/// The context in which an event was received, passed to each method of an
+/// [`EventHandler`].
+///
+/// [`EventHandler`]: trait.EventHandler.html
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct Context {
+    /// The HTTP client, used to make requests to the REST API.
+    pub http: Arc<Http>,
+    /// The ID of the shard the event was received on.
+    pub shard_id: u64,
+}
+
+impl Context {
+    pub(crate) fn new(http: Arc<Http>, shard_id: u64) -> Context {
+        Context { http, shard_id }
+    }
+}
@@ -1,2 +1,199 @@
+use async_trait::async_trait;
+
+use crate::model::channel::{Channel, Message};
+use crate::model::gateway::event::*;
+use crate::model::gateway::presence::Presence;
+use crate::model::guild::{Guild, UnavailableGuild};
+use crate::model::user::ClientUser;
+use crate::model::voice::VoiceState;
+
+use super::Context;
+
 /// Trait for handling events for a `Client`.
-pub trait EventHandler {}
+///
+/// Each method corresponds to a gateway event and has a default implementation
+/// that does nothing, so only the events of interest need to be implemented.
+///
+/// # Examples
+///
+/// ```
+/// use strife::client::{async_trait, Context, EventHandler};
+/// use strife::model::channel::Message;
+///
+/// struct Handler;
+///
+/// #[async_trait]
+/// impl EventHandler for Handler {
+///     async fn message_create(&self, _ctx: Context, message: Message) {
+///         println!("{}", message.content);
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    /// Dispatched when a channel is created.
+    async fn channel_create(&self, _ctx: Context, _channel: Channel) {}
+
+    /// Dispatched when a channel is deleted.
+    async fn channel_delete(&self, _ctx: Context, _channel: Channel) {}
+
+    /// Dispatched when a message is pinned or unpinned in a channel.
+    async fn channel_pins_update(&self, _ctx: Context, _event: ChannelPinsUpdateEvent) {}
+
+    /// Dispatched when a channel is updated.
+    async fn channel_update(&self, _ctx: Context, _channel: Channel) {}
+
+    /// Dispatched when a user is banned from a guild.
+    async fn guild_ban_add(&self, _ctx: Context, _event: GuildBanEvent) {}
+
+    /// Dispatched when a user is unbanned from a guild.
+    async fn guild_ban_remove(&self, _ctx: Context, _event: GuildBanEvent) {}
+
+    /// Dispatched when a guild becomes available, or the client user joins a
+    /// guild.
+    async fn guild_create(&self, _ctx: Context, _guild: Guild) {}
+
+    /// Dispatched when a guild becomes unavailable, or the client user leaves
+    /// or is removed from a guild.
+    async fn guild_delete(&self, _ctx: Context, _guild: UnavailableGuild) {}
+
+    /// Dispatched when the emojis of a guild are updated.
+    async fn guild_emojis_update(&self, _ctx: Context, _event: GuildEmojisUpdateEvent) {}
+
+    /// Dispatched when the integrations of a guild are updated.
+    async fn guild_integrations_update(&self, _ctx: Context, _event: GuildIntegrationsUpdateEvent) {
+    }
+
+    /// Dispatched when a user joins a guild.
+    async fn guild_member_add(&self, _ctx: Context, _event: GuildMemberAddEvent) {}
+
+    /// Dispatched when a user is removed from a guild.
+    async fn guild_member_remove(&self, _ctx: Context, _event: GuildMemberRemoveEvent) {}
+
+    /// Dispatched when a member of a guild is updated.
+    async fn guild_member_update(&self, _ctx: Context, _event: GuildMemberUpdateEvent) {}
+
+    /// Dispatched when a chunk of requested guild members is received.
+    async fn guild_members_chunk(&self, _ctx: Context, _event: GuildMembersChunkEvent) {}
+
+    /// Dispatched when a role is created in a guild.
+    async fn guild_role_create(&self, _ctx: Context, _event: GuildRoleEvent) {}
+
+    /// Dispatched when a role is deleted from a guild.
+    async fn guild_role_delete(&self, _ctx: Context, _event: GuildRoleDeleteEvent) {}
+
+    /// Dispatched when a role is updated in a guild.
+    async fn guild_role_update(&self, _ctx: Context, _event: GuildRoleEvent) {}
+
+    /// Dispatched when a guild is updated.
+    async fn guild_update(&self, _ctx: Context, _guild: Guild) {}
+
+    /// Dispatched when a message is created.
+    async fn message_create(&self, _ctx: Context, _message: Message) {}
+
+    /// Dispatched when a message is deleted.
+    async fn message_delete(&self, _ctx: Context, _event: MessageDeleteEvent) {}
+
+    /// Dispatched when multiple messages are deleted at once.
+    async fn message_delete_bulk(&self, _ctx: Context, _event: MessageDeleteBulkEvent) {}
+
+    /// Dispatched when a user reacts to a message.
+    async fn message_reaction_add(&self, _ctx: Context, _event: MessageReactionEvent) {}
+
+    /// Dispatched when a user removes a reaction from a message.
+    async fn message_reaction_remove(&self, _ctx: Context, _event: MessageReactionEvent) {}
+
+    /// Dispatched when all reactions are removed from a message.
+    async fn message_reaction_remove_all(
+        &self,
+        _ctx: Context,
+        _event: MessageReactionRemoveAllEvent,
+    ) {
+    }
+
+    /// Dispatched when all reactions of a single emoji are removed from a
+    /// message.
+    async fn message_reaction_remove_emoji(
+        &self,
+        _ctx: Context,
+        _event: MessageReactionRemoveEmojiEvent,
+    ) {
+    }
+
+    /// Dispatched when a message is updated.
+    async fn message_update(&self, _ctx: Context, _event: MessageUpdateEvent) {}
+
+    /// Dispatched when the presence of a user is updated.
+    async fn presence_update(&self, _ctx: Context, _presence: Presence) {}
+
+    /// Dispatched when a shard has connected and identified.
+    async fn ready(&self, _ctx: Context, _ready: ReadyEvent) {}
+
+    /// Dispatched when a shard has resumed a session.
+    async fn resumed(&self, _ctx: Context, _event: ResumedEvent) {}
+
+    /// Dispatched when a user starts typing in a channel.
+    async fn typing_start(&self, _ctx: Context, _event: TypingStartEvent) {}
+
+    /// Dispatched for events not known by the library.
+    async fn unknown(&self, _ctx: Context, _event: UnknownEvent) {}
+
+    /// Dispatched when the client user is updated.
+    async fn user_update(&self, _ctx: Context, _user: ClientUser) {}
+
+    /// Dispatched when the voice server of a guild is updated.
+    async fn voice_server_update(&self, _ctx: Context, _event: VoiceServerUpdateEvent) {}
+
+    /// Dispatched when the voice state of a user is updated.
+    async fn voice_state_update(&self, _ctx: Context, _state: VoiceState) {}
+
+    /// Dispatched when a webhook in a channel is created, updated or deleted.
+    async fn webhooks_update(&self, _ctx: Context, _event: WebhooksUpdateEvent) {}
+}
+
+/// Dispatches an event to the matching method of an event handler.
+pub(crate) async fn dispatch(handler: &dyn EventHandler, ctx: Context, event: Event) {
+    match event {
+        Event::ChannelCreate(channel) => handler.channel_create(ctx, channel).await,
+        Event::ChannelDelete(channel) => handler.channel_delete(ctx, channel).await,
+        Event::ChannelPinsUpdate(event) => handler.channel_pins_update(ctx, event).await,
+        Event::ChannelUpdate(channel) => handler.channel_update(ctx, channel).await,
+        Event::GuildBanAdd(event) => handler.guild_ban_add(ctx, event).await,
+        Event::GuildBanRemove(event) => handler.guild_ban_remove(ctx, event).await,
+        Event::GuildCreate(guild) => handler.guild_create(ctx, *guild).await,
+        Event::GuildDelete(guild) => handler.guild_delete(ctx, guild).await,
+        Event::GuildEmojisUpdate(event) => handler.guild_emojis_update(ctx, event).await,
+        Event::GuildIntegrationsUpdate(event) => {
+            handler.guild_integrations_update(ctx, event).await
+        }
+        Event::GuildMemberAdd(event) => handler.guild_member_add(ctx, event).await,
+        Event::GuildMemberRemove(event) => handler.guild_member_remove(ctx, event).await,
+        Event::GuildMemberUpdate(event) => handler.guild_member_update(ctx, event).await,
+        Event::GuildMembersChunk(event) => handler.guild_members_chunk(ctx, event).await,
+        Event::GuildRoleCreate(event) => handler.guild_role_create(ctx, event).await,
+        Event::GuildRoleDelete(event) => handler.guild_role_delete(ctx, event).await,
+        Event::GuildRoleUpdate(event) => handler.guild_role_update(ctx, event).await,
+        Event::GuildUpdate(guild) => handler.guild_update(ctx, *guild).await,
+        Event::MessageCreate(message) => handler.message_create(ctx, *message).await,
+        Event::MessageDelete(event) => handler.message_delete(ctx, event).await,
+        Event::MessageDeleteBulk(event) => handler.message_delete_bulk(ctx, event).await,
+        Event::MessageReactionAdd(event) => handler.message_reaction_add(ctx, event).await,
+        Event::MessageReactionRemove(event) => handler.message_reaction_remove(ctx, event).await,
+        Event::MessageReactionRemoveAll(event) => {
+            handler.message_reaction_remove_all(ctx, event).await
+        }
+        Event::MessageReactionRemoveEmoji(event) => {
+            handler.message_reaction_remove_emoji(ctx, event).await
+        }
+        Event::MessageUpdate(event) => handler.message_update(ctx, *event).await,
+        Event::PresenceUpdate(presence) => handler.presence_update(ctx, *presence).await,
+        Event::Ready(ready) => handler.ready(ctx, *ready).await,
+        Event::Resumed(event) => handler.resumed(ctx, event).await,
+        Event::TypingStart(event) => handler.typing_start(ctx, event).await,
+        Event::Unknown(event) => handler.unknown(ctx, event).await,
+        Event::UserUpdate(user) => handler.user_update(ctx, user).await,
+        Event::VoiceServerUpdate(event) => handler.voice_server_update(ctx, event).await,
+        Event::VoiceStateUpdate(state) => handler.voice_state_update(ctx, state).await,
+        Event::WebhooksUpdate(event) => handler.webhooks_update(ctx, event).await,
+    }
+}
@@ -4,15 +4,30 @@
 //!
 //! [`Client`]: struct.Client.html
 
+mod context;
 mod event_handler;
 
-use crate::http::Http;
+use std::sync::Arc;
+
+use crate::gateway::{GatewayError, IdentifyLimiter, ShardManager};
+use crate::http::{Http, HttpError};
 use crate::internal::prelude::*;
+use crate::model::gateway::event::Event;
+use crate::model::gateway::BotGatewayInfo;
+
+pub use async_trait::async_trait;
 
+pub use self::context::Context;
 pub use self::event_handler::EventHandler;
 
+use self::event_handler::dispatch;
+
 /// A client that connects to Discord via the WebSocket and API.
-pub struct Client {}
+pub struct Client {
+    token: String,
+    http: Arc<Http>,
+    handler: Arc<dyn EventHandler>,
+}
 
 impl Client {
     /// Creates a Client for a bot user.
@@ -26,6 +41,7 @@ impl Client {
     /// struct Handler;
     ///
     /// # use strife::client::EventHandler;
+    /// #[strife::client::async_trait]
     /// impl EventHandler for Handler {}
     ///
     /// use std::env;
@@ -38,19 +54,156 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn new<S, H>(token: S, _handler: H) -> Result<Client>
+    pub fn new<S, H>(token: S, handler: H) -> Result<Client>
     where
         S: AsRef<str>,
         H: EventHandler + Send + Sync + 'static,
     {
-        let _http = Http::new(token);
+        Client::with_http(Http::new(token), handler)
+    }
+
+    /// Creates a Client for a bot user, that sends requests with the given
+    /// HTTP client.
+    ///
+    /// This allows using a client built by an [`HttpBuilder`], eg. with a
+    /// custom ratelimiter. The gateway is connected to with the API token of
+    /// the HTTP client.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MissingToken`] error if the HTTP client has no API token.
+    ///
+    /// [`HttpBuilder`]: ../http/struct.HttpBuilder.html
+    /// [`MissingToken`]: ../http/enum.HttpError.html#variant.MissingToken
+    pub fn with_http<H>(http: Http, handler: H) -> Result<Client>
+    where
+        H: EventHandler + Send + Sync + 'static,
+    {
+        let token = match http.token() {
+            Some(token) => token.to_owned(),
+            None => return Err(HttpError::MissingToken.into()),
+        };
 
         // TODO: thread pool
         let _name = concat!(pkg_name!(), " client");
-        // TODO: event handler
 
-        // TODO: shard manager
+        Ok(Client {
+            token,
+            http: Arc::new(http),
+            handler: Arc::new(handler),
+        })
+    }
+
+    /// Connects to the gateway and starts receiving events, using the number
+    /// of shards recommended by Discord.
+    ///
+    /// Events are dispatched to the event handler in the order they are
+    /// received.
+    ///
+    /// If Discord requires more shards while running, all shards are
+    /// restarted with the new recommended number of shards.
+    ///
+    /// This will only return if the connection fails in a way that cannot be
+    /// recovered from, such as an invalid token.
+    pub async fn start(&mut self) -> Result<()> {
+        loop {
+            let gateway = self.http.get_bot_gateway().await?;
+            let shards = gateway.shards;
+
+            match self.run(gateway, shards).await {
+                Err(Error::GatewayError(GatewayError::ShardingRequired)) => {
+                    log::warn!("Sharding required, restarting shards");
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Connects to the gateway and starts receiving events, using the given
+    /// number of shards.
+    ///
+    /// See [`start`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidShard`] error if `shards` is `0`.
+    ///
+    /// [`start`]: #method.start
+    /// [`InvalidShard`]: ../gateway/enum.GatewayError.html#variant.InvalidShard
+    pub async fn start_shards(&mut self, shards: u64) -> Result<()> {
+        if shards == 0 {
+            return Err(GatewayError::InvalidShard.into());
+        }
+
+        let gateway = self.http.get_bot_gateway().await?;
+        self.run(gateway, shards).await
+    }
+
+    async fn run(&self, gateway: BotGatewayInfo, shards: u64) -> Result<()> {
+        log::info!("Starting {} shards", shards);
+
+        let limiter = Arc::new(IdentifyLimiter::new(&gateway.session_start_limit));
+        let mut manager = ShardManager::new(&gateway.url, &self.token, shards, limiter);
+        manager.start();
+
+        loop {
+            let (shard_id, payload) = match manager.next_event().await {
+                Ok(event) => event,
+                Err(err) => {
+                    // Stop the remaining shards before returning, so they are
+                    // not left connected if the shards are restarted.
+                    manager.shutdown();
+                    return Err(err);
+                }
+            };
+            log::trace!("[Shard {:?}] Received {} event", shard_id, payload.kind);
+
+            let event = match Event::from_dispatch(&payload.kind, payload.data) {
+                Ok(event) => event,
+                Err(err) => {
+                    log::warn!(
+                        "[Shard {:?}] Failed to deserialize {} event: {}",
+                        shard_id,
+                        payload.kind,
+                        err
+                    );
+                    continue;
+                }
+            };
+
+            let ctx = Context::new(Arc::clone(&self.http), shard_id);
+            dispatch(&*self.handler, ctx, event).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Handler;
+
+    impl EventHandler for Handler {}
+
+    #[test]
+    fn test_with_http_token() {
+        let http = Http::builder().token("token").build().unwrap();
+        let client = Client::with_http(http, Handler).unwrap();
+        assert_eq!(client.token, "Bot token");
+
+        let http = Http::builder().build().unwrap();
+        match Client::with_http(http, Handler) {
+            Err(Error::HttpError(HttpError::MissingToken)) => {}
+            _ => panic!("expected missing token error"),
+        }
+    }
 
-        Ok(Client {})
+    #[async_std::test]
+    async fn test_start_no_shards() {
+        let mut client = Client::new("token", Handler).unwrap();
+        match client.start_shards(0).await {
+            Err(Error::GatewayError(GatewayError::InvalidShard)) => {}
+            _ => panic!("expected invalid shard error"),
+        }
     }
 }
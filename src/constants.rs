@@ -3,10 +3,60 @@
 /// The gateway version used by the library, URI is retrieved via the REST API.
 pub const GATEWAY_VERSION: usize = 6;
 
+/// The number of members in a guild, after which offline members will not be
+/// sent in the initial guild member list.
+pub const LARGE_THRESHOLD: u64 = 250;
+
 /// The maximum length of textual size of an embed message.
 pub const EMBED_MAX_LENGTH: usize = 6000;
+/// The maximum number of fields in an embed.
+pub const EMBED_MAX_FIELDS: usize = 25;
+/// The maximum length of an embed title.
+pub const EMBED_TITLE_MAX_LENGTH: usize = 256;
+/// The maximum length of an embed description.
+pub const EMBED_DESCRIPTION_MAX_LENGTH: usize = 2048;
+/// The maximum length of the name of an embed field.
+pub const EMBED_FIELD_NAME_MAX_LENGTH: usize = 256;
+/// The maximum length of the value of an embed field.
+pub const EMBED_FIELD_VALUE_MAX_LENGTH: usize = 1024;
+/// The maximum length of the text of an embed footer.
+pub const EMBED_FOOTER_TEXT_MAX_LENGTH: usize = 2048;
+/// The maximum length of the name of an embed author.
+pub const EMBED_AUTHOR_NAME_MAX_LENGTH: usize = 256;
+/// The maximum length of an audit log reason in Unicode code points allowed by
+/// Discord.
+pub const AUDIT_LOG_REASON_MAX_LENGTH: usize = 512;
+/// The maximum number of days of messages that can be deleted when banning a
+/// user.
+pub const BAN_DELETE_MESSAGE_DAYS_MAX: u64 = 7;
+/// The maximum length of a channel name in Unicode code points allowed by
+/// Discord.
+pub const CHANNEL_NAME_MAX_LENGTH: usize = 100;
+/// The maximum length of a channel topic in Unicode code points allowed by
+/// Discord.
+pub const CHANNEL_TOPIC_MAX_LENGTH: usize = 1024;
+/// The maximum number of seconds a user has to wait between sending messages
+/// in a channel allowed by Discord.
+pub const CHANNEL_RATE_LIMIT_MAX: u64 = 21_600;
+/// The maximum number of invalid requests, responded to with a 401, 403 or 429
+/// status, allowed by Discord in 10 minutes before the IP address is
+/// temporarily banned.
+pub const INVALID_REQUEST_LIMIT: u32 = 10_000;
+/// The maximum duration in seconds before an invite expires allowed by Discord.
+pub const INVITE_MAX_AGE: u64 = 604_800;
+/// The maximum number of uses of an invite allowed by Discord.
+pub const INVITE_MAX_USES: u64 = 100;
 /// The maximum length of a message in Unicode code points allowed by Discord.
 pub const MESSAGE_MAX_LENGTH: usize = 2000;
+/// The maximum length of a member nickname in Unicode code points allowed by
+/// Discord.
+pub const NICKNAME_MAX_LENGTH: usize = 32;
+/// The maximum length of a role name in Unicode code points allowed by Discord.
+pub const ROLE_NAME_MAX_LENGTH: usize = 100;
+/// The maximum number of users in a voice channel allowed by Discord.
+pub const VOICE_USER_LIMIT_MAX: u64 = 99;
+/// The maximum number of embeds in a message sent by a webhook.
+pub const WEBHOOK_MAX_EMBEDS: usize = 10;
 
 /// The UserAgent header sent with every request.
 pub const USER_AGENT: &str = concat!("DiscordBot (", pkg_repo!(), ", ", pkg_version!(), ")");
@@ -4,6 +4,8 @@ use std::result::Result as StdResult;
 use serde_json::Error as JsonError;
 use thiserror::Error;
 
+use crate::builder::ValidationError;
+use crate::gateway::GatewayError;
 use crate::http::HttpError;
 
 /// The common result type returned by library functions.
@@ -18,10 +20,16 @@ pub enum Error {
     /// An error while formatting a message.
     #[error(transparent)]
     Format(#[from] FmtError),
+    /// A gateway error.
+    #[error(transparent)]
+    GatewayError(#[from] GatewayError),
     /// An HTTP error.
     #[error(transparent)]
     HttpError(#[from] HttpError),
     /// A JSON error.
     #[error(transparent)]
     JsonError(#[from] JsonError),
+    /// A request failed validation before being sent.
+    #[error(transparent)]
+    ValidationError(#[from] ValidationError),
 }
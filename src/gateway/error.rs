@@ -0,0 +1,22 @@
+use async_tungstenite::tungstenite::Error as WsError;
+use thiserror::Error;
+
+/// A gateway error.
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The token sent with the identify payload was rejected.
+    #[error("gateway authentication failed")]
+    AuthenticationFailed,
+    /// An invalid shard was sent when identifying, or the number of shards to
+    /// start was `0`.
+    #[error("invalid shard")]
+    InvalidShard,
+    /// The session would have handled too many guilds, more shards are
+    /// required.
+    #[error("sharding required")]
+    ShardingRequired,
+    /// A WebSocket error.
+    #[error(transparent)]
+    WebSocket(#[from] WsError),
+}
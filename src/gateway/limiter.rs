@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use async_std::sync::Mutex;
+use futures_timer::Delay;
+
+use crate::model::gateway::SessionStartLimit;
+
+/// The minimum delay between identifying shards.
+#[cfg(not(test))]
+const IDENTIFY_DELAY: Duration = Duration::from_secs(5);
+#[cfg(test)]
+const IDENTIFY_DELAY: Duration = Duration::from_millis(50);
+
+/// The period after which the session start limit resets, if Discord has not
+/// provided a reset time.
+const SESSION_START_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Limits the rate at which shards identify with the gateway, shared between
+/// all shards of a client.
+///
+/// Identifies are spaced by at least 5 seconds, and will wait for the session
+/// start limit to reset once it has been exhausted.
+pub(crate) struct IdentifyLimiter {
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    /// When the last identify was sent.
+    last: Option<Instant>,
+    /// The total number of session starts allowed in a period.
+    total: u64,
+    /// The number of session starts remaining in the current period.
+    remaining: u64,
+    /// When the current period ends.
+    reset_at: Instant,
+}
+
+impl IdentifyLimiter {
+    /// Creates a new limiter from the session start limit provided by the
+    /// `/gateway/bot` route.
+    pub fn new(limit: &SessionStartLimit) -> IdentifyLimiter {
+        IdentifyLimiter {
+            state: Mutex::new(LimiterState {
+                last: None,
+                total: limit.total,
+                remaining: limit.remaining,
+                reset_at: Instant::now() + Duration::from_millis(limit.reset_after),
+            }),
+        }
+    }
+
+    /// Waits until a shard is allowed to identify.
+    ///
+    /// The lock is held while waiting, so that identifies are sent one at a
+    /// time.
+    pub async fn acquire(&self) {
+        let mut state = self.state.lock().await;
+
+        if state.remaining == 0 {
+            let now = Instant::now();
+            if state.reset_at > now {
+                let wait = state.reset_at - now;
+                log::warn!(
+                    "Session start limit exhausted, waiting {:?} before identifying",
+                    wait
+                );
+                Delay::new(wait).await;
+            }
+
+            state.remaining = state.total;
+            state.reset_at = Instant::now() + SESSION_START_PERIOD;
+        }
+
+        if let Some(last) = state.last {
+            let next = last + IDENTIFY_DELAY;
+            let now = Instant::now();
+            if next > now {
+                Delay::new(next - now).await;
+            }
+        }
+
+        state.remaining = state.remaining.saturating_sub(1);
+        state.last = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[async_std::test]
+    async fn test_identify_delay() {
+        let limiter = IdentifyLimiter::new(&SessionStartLimit {
+            total: 1000,
+            remaining: 1000,
+            reset_after: 0,
+        });
+
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert!(start.elapsed() < IDENTIFY_DELAY);
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() >= IDENTIFY_DELAY * 2);
+    }
+
+    #[async_std::test]
+    async fn test_session_start_limit() {
+        let reset_after = IDENTIFY_DELAY * 4;
+        let limiter = IdentifyLimiter::new(&SessionStartLimit {
+            total: 1000,
+            remaining: 1,
+            reset_after: reset_after.as_millis() as u64,
+        });
+
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() >= reset_after);
+
+        let state = limiter.state.lock().await;
+        assert_eq!(state.remaining, 999);
+    }
+}
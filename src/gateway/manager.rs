@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::task;
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures_timer::Delay;
+use futures_util::future::{AbortHandle, Abortable};
+use futures_util::StreamExt;
+
+use crate::internal::prelude::*;
+
+use super::shard::{Dispatch, Session, Shard};
+use super::{GatewayError, IdentifyLimiter};
+
+/// The delay before restarting a shard that stopped unexpectedly.
+#[cfg(not(test))]
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+#[cfg(test)]
+const RESTART_DELAY: Duration = Duration::from_millis(10);
+
+/// A message sent from a shard task to the manager.
+enum ShardMessage {
+    /// An event was dispatched to the shard.
+    Dispatch(u64, Dispatch),
+    /// The shard stopped with an error, with the session it had, if any.
+    Stopped(u64, Error, Option<Session>),
+}
+
+/// Manages a set of shards, each running in its own task.
+///
+/// Shards that stop with a recoverable error are restarted, resuming their
+/// session if possible. All shards share the same [`IdentifyLimiter`] to
+/// respect the identify rate limits.
+///
+/// Shard tasks are stopped when the manager is dropped.
+///
+/// [`IdentifyLimiter`]: struct.IdentifyLimiter.html
+pub(crate) struct ShardManager {
+    url: String,
+    token: String,
+    total: u64,
+    limiter: Arc<IdentifyLimiter>,
+    tx: UnboundedSender<ShardMessage>,
+    rx: UnboundedReceiver<ShardMessage>,
+    /// Handles to stop the task of each shard.
+    tasks: HashMap<u64, AbortHandle>,
+}
+
+impl ShardManager {
+    /// Creates a manager for the given total number of shards.
+    pub fn new(url: &str, token: &str, total: u64, limiter: Arc<IdentifyLimiter>) -> ShardManager {
+        let (tx, rx) = mpsc::unbounded();
+
+        ShardManager {
+            url: url.to_owned(),
+            token: token.to_owned(),
+            total,
+            limiter,
+            tx,
+            rx,
+            tasks: HashMap::new(),
+        }
+    }
+
+    /// Starts all shards.
+    ///
+    /// Shards are started in order, and will identify at most once every 5
+    /// seconds.
+    pub fn start(&mut self) {
+        for id in 0..self.total {
+            self.spawn(id, None, None);
+        }
+    }
+
+    /// Stops all shards.
+    pub fn shutdown(&mut self) {
+        for (_, task) in self.tasks.drain() {
+            task.abort();
+        }
+    }
+
+    /// Waits for the next event to be dispatched to any of the shards,
+    /// returning the ID of the shard and the event.
+    ///
+    /// Shards that stopped are restarted, unless the error cannot be recovered
+    /// from by restarting, in which case the error is returned.
+    pub async fn next_event(&mut self) -> Result<(u64, Dispatch)> {
+        loop {
+            // The manager holds a sender, so the channel is never closed.
+            let message = match self.rx.next().await {
+                Some(message) => message,
+                None => unreachable!("shard channel closed"),
+            };
+
+            match message {
+                ShardMessage::Dispatch(id, dispatch) => return Ok((id, dispatch)),
+                ShardMessage::Stopped(id, err, session) => match err {
+                    Error::GatewayError(GatewayError::AuthenticationFailed)
+                    | Error::GatewayError(GatewayError::InvalidShard)
+                    | Error::GatewayError(GatewayError::ShardingRequired) => return Err(err),
+                    err => {
+                        log::warn!("[Shard {:?}] Stopped: {}, restarting", self.info(id), err);
+                        self.spawn(id, Some(RESTART_DELAY), session);
+                    }
+                },
+            }
+        }
+    }
+
+    fn info(&self, id: u64) -> [u64; 2] {
+        [id, self.total]
+    }
+
+    fn spawn(&mut self, id: u64, delay: Option<Duration>, mut session: Option<Session>) {
+        let url = self.url.clone();
+        let token = self.token.clone();
+        let info = self.info(id);
+        let limiter = Arc::clone(&self.limiter);
+        let tx = self.tx.clone();
+
+        let (handle, registration) = AbortHandle::new_pair();
+        let task = async move {
+            if let Some(delay) = delay {
+                Delay::new(delay).await;
+            }
+
+            if let Err(err) = run_shard(&url, &token, info, limiter, &mut session, &tx).await {
+                let _ = tx.unbounded_send(ShardMessage::Stopped(id, err, session));
+            }
+        };
+        task::spawn(Abortable::new(task, registration));
+
+        if let Some(old) = self.tasks.insert(id, handle) {
+            old.abort();
+        }
+    }
+}
+
+impl Drop for ShardManager {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Runs a shard, forwarding events to the manager until the shard stops or the
+/// manager is dropped.
+///
+/// The shard resumes the given session, which is updated if the shard stops
+/// with an error.
+async fn run_shard(
+    url: &str,
+    token: &str,
+    info: [u64; 2],
+    limiter: Arc<IdentifyLimiter>,
+    session: &mut Option<Session>,
+    tx: &UnboundedSender<ShardMessage>,
+) -> Result<()> {
+    let mut shard = Shard::new(url, token, info, limiter).await?;
+    if let Some(session) = session.clone() {
+        shard.set_session(session);
+    }
+
+    loop {
+        let dispatch = match shard.next_event().await {
+            Ok(dispatch) => dispatch,
+            Err(err) => {
+                *session = shard.session();
+                return Err(err);
+            }
+        };
+        if tx
+            .unbounded_send(ShardMessage::Dispatch(info[0], dispatch))
+            .is_err()
+        {
+            log::debug!("[Shard {:?}] Manager dropped, stopping", info);
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use async_std::net::{TcpListener, TcpStream};
+    use async_tungstenite::tungstenite::Message as WsMessage;
+    use async_tungstenite::WebSocketStream;
+    use futures_util::SinkExt;
+    use serde_json::{json, Value};
+
+    use crate::model::gateway::SessionStartLimit;
+
+    use super::*;
+
+    type ServerStream = WebSocketStream<TcpStream>;
+
+    fn limiter() -> Arc<IdentifyLimiter> {
+        Arc::new(IdentifyLimiter::new(&SessionStartLimit {
+            total: 1000,
+            remaining: 1000,
+            reset_after: 0,
+        }))
+    }
+
+    async fn accept(listener: &TcpListener) -> ServerStream {
+        let (stream, _) = listener.accept().await.unwrap();
+        async_tungstenite::accept_async(stream).await.unwrap()
+    }
+
+    async fn send(ws: &mut ServerStream, value: Value) {
+        ws.send(WsMessage::Text(value.to_string())).await.unwrap();
+    }
+
+    async fn recv(ws: &mut ServerStream) -> Value {
+        loop {
+            match ws.next().await {
+                Some(Ok(WsMessage::Text(text))) => return serde_json::from_str(&text).unwrap(),
+                Some(Ok(_)) => continue,
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+    }
+
+    /// Accepts a connection and waits for the shard to identify, returning the
+    /// shard info it identified with.
+    async fn identify(listener: &TcpListener) -> (ServerStream, Value) {
+        let mut ws = accept(listener).await;
+        send(
+            &mut ws,
+            json!({"op": 10, "d": {"heartbeat_interval": 45000}}),
+        )
+        .await;
+
+        let identify = recv(&mut ws).await;
+        assert_eq!(identify["op"], 2);
+        let info = identify["d"]["shard"].clone();
+        (ws, info)
+    }
+
+    #[async_std::test]
+    async fn test_start_shards() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let mut manager = ShardManager::new(&url, "token", 2, limiter());
+        manager.start();
+
+        let start = Instant::now();
+        let mut shards = Vec::new();
+        for _ in 0..2 {
+            let (mut ws, info) = identify(&listener).await;
+            let id = info[0].as_u64().unwrap();
+            assert_eq!(info[1], 2);
+
+            send(
+                &mut ws,
+                json!({"op": 0, "s": 1, "t": "READY", "d": {"shard": info}}),
+            )
+            .await;
+            shards.push((id, ws));
+        }
+        // The second shard must wait before identifying.
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let (id, dispatch) = manager.next_event().await.unwrap();
+            assert_eq!(dispatch.kind, "READY");
+            assert_eq!(dispatch.data["shard"][0], id);
+            ids.push(id);
+        }
+        ids.sort();
+        assert_eq!(ids, vec![0, 1]);
+    }
+
+    #[async_std::test]
+    async fn test_restart_shard() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let mut manager = ShardManager::new(&url, "token", 1, limiter());
+        manager.start();
+
+        let server = task::spawn(async move {
+            let (mut ws, _) = identify(&listener).await;
+            send(
+                &mut ws,
+                json!({"op": 0, "s": 1, "t": "READY", "d": {"session_id": "abc"}}),
+            )
+            .await;
+            // An invalid hello causes the shard to stop.
+            send(&mut ws, json!({"op": 10, "d": {}})).await;
+
+            // The restarted shard resumes the session.
+            let mut ws = accept(&listener).await;
+            send(
+                &mut ws,
+                json!({"op": 10, "d": {"heartbeat_interval": 45000}}),
+            )
+            .await;
+            let resume = recv(&mut ws).await;
+            assert_eq!(resume["op"], 6);
+            assert_eq!(resume["d"]["session_id"], "abc");
+            assert_eq!(resume["d"]["seq"], 1);
+            send(&mut ws, json!({"op": 0, "s": 2, "t": "RESUMED", "d": {}})).await;
+            ws
+        });
+
+        let (id, dispatch) = manager.next_event().await.unwrap();
+        assert_eq!(id, 0);
+        assert_eq!(dispatch.kind, "READY");
+
+        let (id, dispatch) = manager.next_event().await.unwrap();
+        assert_eq!(id, 0);
+        assert_eq!(dispatch.kind, "RESUMED");
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_shutdown_on_drop() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let mut manager = ShardManager::new(&url, "token", 1, limiter());
+        manager.start();
+
+        let (mut ws, _) = identify(&listener).await;
+        drop(manager);
+
+        // The connection is closed once the shard task is stopped.
+        loop {
+            match ws.next().await {
+                Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            }
+        }
+    }
+
+    #[async_std::test]
+    async fn test_sharding_required() {
+        use async_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+        use async_tungstenite::tungstenite::protocol::CloseFrame;
+
+        use crate::constants::close_codes;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let mut manager = ShardManager::new(&url, "token", 1, limiter());
+        manager.start();
+
+        let server = task::spawn(async move {
+            let (mut ws, _) = identify(&listener).await;
+            let frame = CloseFrame {
+                code: CloseCode::from(close_codes::SHARDING_REQUIRED),
+                reason: "".into(),
+            };
+            ws.close(Some(frame)).await.unwrap();
+            ws
+        });
+
+        match manager.next_event().await {
+            Err(Error::GatewayError(GatewayError::ShardingRequired)) => {}
+            other => panic!("unexpected result: {:?}", other.map(|(id, _)| id)),
+        }
+
+        server.await;
+    }
+}
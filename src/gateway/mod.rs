@@ -0,0 +1,15 @@
+//! The gateway module handles the WebSocket connections to the Discord
+//! gateway, over which events are received.
+//!
+//! Connections are made through shards, each shard receiving events for a
+//! subset of the guilds the bot is in.
+
+mod error;
+mod limiter;
+mod manager;
+mod shard;
+
+pub use self::error::Error as GatewayError;
+
+pub(crate) use self::limiter::IdentifyLimiter;
+pub(crate) use self::manager::ShardManager;
@@ -0,0 +1,913 @@
+use std::env::consts::OS;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_native_tls::TlsStream;
+use async_std::future::timeout;
+use async_std::net::TcpStream;
+use async_tungstenite::stream::Stream;
+use async_tungstenite::tungstenite::protocol::CloseFrame;
+use async_tungstenite::tungstenite::Message as WsMessage;
+use async_tungstenite::WebSocketStream;
+use futures_timer::Delay;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::constants::{self, close_codes, OpCode};
+use crate::internal::prelude::*;
+
+use super::{GatewayError, IdentifyLimiter};
+
+type WsStream = WebSocketStream<Stream<TcpStream, TlsStream<TcpStream>>>;
+
+/// The delay before identifying again after receiving an invalid session.
+///
+/// Discord recommends waiting a random amount of time between 1 and 5 seconds.
+#[cfg(not(test))]
+const INVALID_SESSION_DELAY: Duration = Duration::from_secs(5);
+#[cfg(test)]
+const INVALID_SESSION_DELAY: Duration = Duration::from_millis(10);
+
+/// The delay before the first retry of a failed reconnect, doubled after each
+/// failed attempt.
+#[cfg(not(test))]
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+#[cfg(test)]
+const RECONNECT_DELAY: Duration = Duration::from_millis(10);
+
+/// The number of times a failed reconnect is retried before giving up.
+const RECONNECT_RETRIES: u32 = 5;
+
+/// A payload sent or received over the gateway.
+#[derive(Debug, Deserialize)]
+struct Payload {
+    op: OpCode,
+    #[serde(default)]
+    d: Value,
+    #[serde(default)]
+    s: Option<u64>,
+    #[serde(default)]
+    t: Option<String>,
+}
+
+/// The data of a [`Hello`] payload.
+///
+/// [`Hello`]: ../constants/enum.OpCode.html#variant.Hello
+#[derive(Debug, Deserialize)]
+struct Hello {
+    heartbeat_interval: u64,
+}
+
+/// An event dispatched by the gateway.
+#[derive(Debug)]
+pub(crate) struct Dispatch {
+    /// The name of the event.
+    pub kind: String,
+    /// The event data.
+    pub data: Value,
+}
+
+/// A gateway session, that can be resumed by a new shard.
+#[derive(Clone, Debug)]
+pub(crate) struct Session {
+    /// The ID of the session.
+    pub id: String,
+    /// The sequence number of the last event received.
+    pub seq: u64,
+}
+
+/// A single WebSocket connection to the gateway.
+pub(crate) struct Shard {
+    /// The shard ID and the total number of shards.
+    info: [u64; 2],
+    token: String,
+    url: String,
+    limiter: Arc<IdentifyLimiter>,
+    client: WsStream,
+    heartbeat_interval: Option<Duration>,
+    last_heartbeat: Option<Instant>,
+    heartbeat_acked: bool,
+    /// The ID of the current session, used for resuming.
+    session_id: Option<String>,
+    /// The sequence number of the last event received.
+    seq: Option<u64>,
+}
+
+impl Shard {
+    /// Connects a new shard to the gateway at the given URL.
+    ///
+    /// Identifying is rate limited by the given limiter, which should be
+    /// shared between all shards.
+    pub async fn new(
+        url: &str,
+        token: &str,
+        info: [u64; 2],
+        limiter: Arc<IdentifyLimiter>,
+    ) -> Result<Shard> {
+        let url = format!(
+            "{}/?v={}&encoding=json",
+            url.trim_end_matches('/'),
+            constants::GATEWAY_VERSION
+        );
+        // The gateway expects the token without the "Bot " prefix.
+        let token = token.trim();
+        let token = token.trim_start_matches("Bot ").to_owned();
+
+        let client = connect(&url).await?;
+
+        Ok(Shard {
+            info,
+            token,
+            url,
+            limiter,
+            client,
+            heartbeat_interval: None,
+            last_heartbeat: None,
+            heartbeat_acked: true,
+            session_id: None,
+            seq: None,
+        })
+    }
+
+    /// Returns the current session, if it can be resumed.
+    pub fn session(&self) -> Option<Session> {
+        match (&self.session_id, self.seq) {
+            (Some(id), Some(seq)) => Some(Session {
+                id: id.clone(),
+                seq,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Sets the session to resume once the gateway says hello, instead of
+    /// identifying with a new session.
+    pub fn set_session(&mut self, session: Session) {
+        self.session_id = Some(session.id);
+        self.seq = Some(session.seq);
+    }
+
+    /// Waits for the next event to be dispatched by the gateway.
+    ///
+    /// Heartbeating, identifying and reconnecting are handled while waiting.
+    pub async fn next_event(&mut self) -> Result<Dispatch> {
+        loop {
+            let message = match self.heartbeat_deadline() {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if deadline <= now {
+                        self.heartbeat().await?;
+                        continue;
+                    }
+
+                    match timeout(deadline - now, self.client.next()).await {
+                        Ok(message) => message,
+                        Err(_) => {
+                            self.heartbeat().await?;
+                            continue;
+                        }
+                    }
+                }
+                None => self.client.next().await,
+            };
+
+            let message = match message {
+                Some(Ok(message)) => message,
+                Some(Err(err)) => {
+                    log::warn!("[Shard {:?}] WebSocket error: {}", self.info, err);
+                    self.reconnect().await?;
+                    continue;
+                }
+                None => {
+                    log::warn!("[Shard {:?}] WebSocket stream ended", self.info);
+                    self.reconnect().await?;
+                    continue;
+                }
+            };
+
+            match message {
+                WsMessage::Text(text) => {
+                    // A malformed payload, or one with an opcode that is not
+                    // known to the library, is skipped rather than ending the
+                    // connection.
+                    let payload: Payload = match serde_json::from_str(&text) {
+                        Ok(payload) => payload,
+                        Err(err) => {
+                            log::warn!(
+                                "[Shard {:?}] Failed to deserialize payload: {}",
+                                self.info,
+                                err
+                            );
+                            continue;
+                        }
+                    };
+                    if let Some(dispatch) = self.handle_payload(payload).await? {
+                        return Ok(dispatch);
+                    }
+                }
+                WsMessage::Close(frame) => self.handle_close(frame).await?,
+                // Pings are answered by tungstenite, and binary payloads are
+                // only sent when compression is requested.
+                _ => {}
+            }
+        }
+    }
+
+    async fn handle_payload(&mut self, payload: Payload) -> Result<Option<Dispatch>> {
+        match payload.op {
+            OpCode::Event => {
+                if let Some(seq) = payload.s {
+                    self.seq = Some(seq);
+                }
+
+                let kind = payload.t.unwrap_or_default();
+                match kind.as_str() {
+                    "READY" => {
+                        self.session_id = payload
+                            .d
+                            .get("session_id")
+                            .and_then(Value::as_str)
+                            .map(str::to_owned);
+                    }
+                    "RESUMED" => log::info!("[Shard {:?}] Resumed session", self.info),
+                    _ => {}
+                }
+
+                return Ok(Some(Dispatch {
+                    kind,
+                    data: payload.d,
+                }));
+            }
+            OpCode::Heartbeat => self.send_heartbeat().await?,
+            OpCode::HeartbeatAck => {
+                log::trace!("[Shard {:?}] Received heartbeat ACK", self.info);
+                self.heartbeat_acked = true;
+            }
+            OpCode::Hello => {
+                let hello = Hello::deserialize(payload.d)?;
+                log::debug!(
+                    "[Shard {:?}] Received hello, heartbeat interval: {}ms",
+                    self.info,
+                    hello.heartbeat_interval
+                );
+
+                self.heartbeat_interval = Some(Duration::from_millis(hello.heartbeat_interval));
+                self.last_heartbeat = Some(Instant::now());
+                self.heartbeat_acked = true;
+
+                if self.can_resume() {
+                    self.resume().await?;
+                } else {
+                    self.identify().await?;
+                }
+            }
+            OpCode::Reconnect => {
+                log::info!("[Shard {:?}] Gateway requested a reconnect", self.info);
+                self.reconnect().await?;
+            }
+            OpCode::InvalidSession => {
+                // The data indicates whether the session can be resumed.
+                let resumable = payload.d.as_bool().unwrap_or(false);
+                log::info!(
+                    "[Shard {:?}] Received invalid session, resumable: {}",
+                    self.info,
+                    resumable
+                );
+
+                Delay::new(INVALID_SESSION_DELAY).await;
+                if resumable && self.can_resume() {
+                    self.resume().await?;
+                } else {
+                    self.reset_session();
+                    self.identify().await?;
+                }
+            }
+            op => log::debug!(
+                "[Shard {:?}] Received unexpected opcode {:?}",
+                self.info,
+                op
+            ),
+        }
+
+        Ok(None)
+    }
+
+    async fn handle_close(&mut self, frame: Option<CloseFrame<'static>>) -> Result<()> {
+        let (code, reason) = match frame {
+            Some(frame) => (frame.code.into(), frame.reason.into_owned()),
+            None => {
+                log::warn!("[Shard {:?}] Connection closed without a code", self.info);
+                return self.reconnect().await;
+            }
+        };
+
+        log::warn!(
+            "[Shard {:?}] Connection closed: {} ({})",
+            self.info,
+            code,
+            reason
+        );
+
+        match code {
+            close_codes::AUTHENTICATION_FAILED => Err(GatewayError::AuthenticationFailed.into()),
+            close_codes::INVALID_SHARD => Err(GatewayError::InvalidShard.into()),
+            close_codes::SHARDING_REQUIRED => Err(GatewayError::ShardingRequired.into()),
+            close_codes::UNKNOWN_OPCODE
+            | close_codes::DECODE_ERROR
+            | close_codes::NOT_AUTHENTICATED
+            | close_codes::ALREADY_AUTHENTICATED => {
+                // These are caused by invalid payloads sent by the library, a
+                // fresh connection is the best that can be done.
+                log::error!(
+                    "[Shard {:?}] Gateway rejected a payload: {} ({})",
+                    self.info,
+                    code,
+                    reason
+                );
+                self.reconnect().await
+            }
+            close_codes::INVALID_SEQUENCE | close_codes::SESSION_TIMEOUT => {
+                // The session cannot be resumed, identify with a new session.
+                self.reset_session();
+                self.reconnect().await
+            }
+            // Any other close code, such as a normal closure, is safe to
+            // reconnect after.
+            _ => self.reconnect().await,
+        }
+    }
+
+    fn heartbeat_deadline(&self) -> Option<Instant> {
+        match (self.heartbeat_interval, self.last_heartbeat) {
+            (Some(interval), Some(last)) => Some(last + interval),
+            _ => None,
+        }
+    }
+
+    async fn heartbeat(&mut self) -> Result<()> {
+        if !self.heartbeat_acked {
+            // The connection has zombied, no ACK was received since the last
+            // heartbeat was sent.
+            log::warn!(
+                "[Shard {:?}] No heartbeat ACK received, reconnecting",
+                self.info
+            );
+            return self.reconnect().await;
+        }
+
+        self.send_heartbeat().await
+    }
+
+    async fn send_heartbeat(&mut self) -> Result<()> {
+        log::trace!("[Shard {:?}] Sending heartbeat", self.info);
+
+        self.send(json!({
+            "op": OpCode::Heartbeat,
+            "d": self.seq,
+        }))
+        .await?;
+
+        self.last_heartbeat = Some(Instant::now());
+        self.heartbeat_acked = false;
+        Ok(())
+    }
+
+    async fn identify(&mut self) -> Result<()> {
+        self.limiter.acquire().await;
+        log::debug!("[Shard {:?}] Identifying", self.info);
+
+        self.send(json!({
+            "op": OpCode::Identify,
+            "d": {
+                "token": self.token,
+                "properties": {
+                    "$os": OS,
+                    "$browser": pkg_name!(),
+                    "$device": pkg_name!(),
+                },
+                "compress": false,
+                "large_threshold": constants::LARGE_THRESHOLD,
+                "shard": self.info,
+            },
+        }))
+        .await
+    }
+
+    async fn resume(&mut self) -> Result<()> {
+        log::debug!(
+            "[Shard {:?}] Resuming session {:?} at sequence {:?}",
+            self.info,
+            self.session_id,
+            self.seq
+        );
+
+        self.send(json!({
+            "op": OpCode::Resume,
+            "d": {
+                "token": self.token,
+                "session_id": self.session_id,
+                "seq": self.seq,
+            },
+        }))
+        .await
+    }
+
+    fn can_resume(&self) -> bool {
+        self.session_id.is_some() && self.seq.is_some()
+    }
+
+    fn reset_session(&mut self) {
+        self.session_id = None;
+        self.seq = None;
+    }
+
+    /// Opens a new connection to the gateway.
+    ///
+    /// The session is kept, so that it can be resumed once the gateway says
+    /// hello. Failed connection attempts are retried with an exponential
+    /// backoff.
+    async fn reconnect(&mut self) -> Result<()> {
+        log::info!("[Shard {:?}] Reconnecting", self.info);
+
+        // The old connection may already be closed, so errors can be ignored.
+        let _ = self.client.close(None).await;
+
+        let mut delay = RECONNECT_DELAY;
+        let mut retries = 0;
+        self.client = loop {
+            match connect(&self.url).await {
+                Ok(client) => break client,
+                Err(err) if retries < RECONNECT_RETRIES => {
+                    log::warn!(
+                        "[Shard {:?}] Failed to reconnect: {}, retrying in {:?}",
+                        self.info,
+                        err,
+                        delay
+                    );
+                    Delay::new(delay).await;
+                    delay *= 2;
+                    retries += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        self.heartbeat_interval = None;
+        self.last_heartbeat = None;
+        self.heartbeat_acked = true;
+        Ok(())
+    }
+
+    async fn send(&mut self, value: Value) -> Result<()> {
+        let text = serde_json::to_string(&value)?;
+        self.client
+            .send(WsMessage::Text(text))
+            .await
+            .map_err(GatewayError::WebSocket)?;
+        Ok(())
+    }
+}
+
+async fn connect(url: &str) -> Result<WsStream> {
+    let (client, _) = async_tungstenite::async_std::connect_async(url)
+        .await
+        .map_err(GatewayError::WebSocket)?;
+    Ok(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_std::net::TcpListener;
+    use async_std::task;
+    use async_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+    use crate::model::gateway::SessionStartLimit;
+
+    use super::*;
+
+    type ServerStream = WebSocketStream<TcpStream>;
+
+    const TOKEN: &str = "Bot test.token";
+
+    fn limiter() -> Arc<IdentifyLimiter> {
+        Arc::new(IdentifyLimiter::new(&SessionStartLimit {
+            total: 1000,
+            remaining: 1000,
+            reset_after: 0,
+        }))
+    }
+
+    async fn bind() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        (listener, url)
+    }
+
+    async fn accept(listener: &TcpListener) -> ServerStream {
+        let (stream, _) = listener.accept().await.unwrap();
+        async_tungstenite::accept_async(stream).await.unwrap()
+    }
+
+    async fn recv(ws: &mut ServerStream) -> Value {
+        loop {
+            match ws.next().await {
+                Some(Ok(WsMessage::Text(text))) => return serde_json::from_str(&text).unwrap(),
+                Some(Ok(_)) => continue,
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+    }
+
+    async fn send(ws: &mut ServerStream, value: Value) {
+        ws.send(WsMessage::Text(value.to_string())).await.unwrap();
+    }
+
+    async fn hello(ws: &mut ServerStream, heartbeat_interval: u64) {
+        send(
+            ws,
+            json!({"op": 10, "d": {"heartbeat_interval": heartbeat_interval}}),
+        )
+        .await;
+    }
+
+    /// Sends a hello and waits for the shard to identify.
+    async fn handshake(ws: &mut ServerStream, heartbeat_interval: u64) -> Value {
+        hello(ws, heartbeat_interval).await;
+
+        let identify = recv(ws).await;
+        assert_eq!(identify["op"], 2);
+        identify
+    }
+
+    async fn close(ws: &mut ServerStream, code: u16) {
+        let frame = CloseFrame {
+            code: CloseCode::from(code),
+            reason: "".into(),
+        };
+        ws.close(Some(frame)).await.unwrap();
+    }
+
+    #[async_std::test]
+    async fn test_identify_and_dispatch() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = accept(&listener).await;
+
+            let identify = handshake(&mut ws, 45000).await;
+            assert_eq!(identify["d"]["token"], "test.token");
+            assert_eq!(identify["d"]["shard"], json!([0, 1]));
+
+            send(
+                &mut ws,
+                json!({"op": 0, "s": 1, "t": "READY", "d": {"session_id": "abc"}}),
+            )
+            .await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        let dispatch = shard.next_event().await.unwrap();
+
+        assert_eq!(dispatch.kind, "READY");
+        assert_eq!(dispatch.data["session_id"], "abc");
+        assert_eq!(shard.seq, Some(1));
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_skip_invalid_payload() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = accept(&listener).await;
+            handshake(&mut ws, 45000).await;
+
+            ws.send(WsMessage::Text("not json".into())).await.unwrap();
+            send(&mut ws, json!({"op": 255, "d": null})).await;
+            send(
+                &mut ws,
+                json!({"op": 0, "s": 1, "t": "READY", "d": {"session_id": "abc"}}),
+            )
+            .await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        let dispatch = shard.next_event().await.unwrap();
+
+        assert_eq!(dispatch.kind, "READY");
+        assert_eq!(shard.seq, Some(1));
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_heartbeat() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = accept(&listener).await;
+            handshake(&mut ws, 20).await;
+
+            send(&mut ws, json!({"op": 0, "s": 5, "t": "RESUMED", "d": {}})).await;
+
+            let heartbeat = recv(&mut ws).await;
+            assert_eq!(heartbeat, json!({"op": 1, "d": 5}));
+            send(&mut ws, json!({"op": 11})).await;
+
+            // Heartbeat requested by the gateway.
+            send(&mut ws, json!({"op": 1})).await;
+            let heartbeat = recv(&mut ws).await;
+            assert_eq!(heartbeat, json!({"op": 1, "d": 5}));
+            send(&mut ws, json!({"op": 11})).await;
+
+            send(
+                &mut ws,
+                json!({"op": 0, "s": 6, "t": "TYPING_START", "d": {}}),
+            )
+            .await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+
+        assert_eq!(shard.next_event().await.unwrap().kind, "RESUMED");
+        assert_eq!(shard.next_event().await.unwrap().kind, "TYPING_START");
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_reconnect_opcode() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = accept(&listener).await;
+            handshake(&mut ws, 45000).await;
+            send(&mut ws, json!({"op": 7})).await;
+
+            let mut ws = accept(&listener).await;
+            handshake(&mut ws, 45000).await;
+            send(&mut ws, json!({"op": 0, "s": 1, "t": "READY", "d": {}})).await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_invalid_session() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = accept(&listener).await;
+            handshake(&mut ws, 45000).await;
+            send(&mut ws, json!({"op": 9, "d": false})).await;
+
+            let identify = recv(&mut ws).await;
+            assert_eq!(identify["op"], 2);
+            send(&mut ws, json!({"op": 0, "s": 1, "t": "READY", "d": {}})).await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_close_reconnect() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = accept(&listener).await;
+            handshake(&mut ws, 45000).await;
+            close(&mut ws, close_codes::UNKNOWN_ERROR).await;
+
+            let mut ws = accept(&listener).await;
+            handshake(&mut ws, 45000).await;
+            send(&mut ws, json!({"op": 0, "s": 1, "t": "READY", "d": {}})).await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+
+        server.await;
+    }
+
+    /// Accepts a connection, identifies and starts a session.
+    async fn start_session(listener: &TcpListener) -> ServerStream {
+        let mut ws = accept(listener).await;
+        handshake(&mut ws, 45000).await;
+        send(
+            &mut ws,
+            json!({"op": 0, "s": 1, "t": "READY", "d": {"session_id": "abc"}}),
+        )
+        .await;
+        ws
+    }
+
+    #[async_std::test]
+    async fn test_resume_after_reconnect() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = start_session(&listener).await;
+            send(
+                &mut ws,
+                json!({"op": 0, "s": 2, "t": "TYPING_START", "d": {}}),
+            )
+            .await;
+            send(&mut ws, json!({"op": 7})).await;
+
+            let mut ws = accept(&listener).await;
+            hello(&mut ws, 45000).await;
+
+            let resume = recv(&mut ws).await;
+            assert_eq!(
+                resume,
+                json!({
+                    "op": 6,
+                    "d": {"token": "test.token", "session_id": "abc", "seq": 2},
+                })
+            );
+            send(&mut ws, json!({"op": 0, "s": 3, "t": "RESUMED", "d": {}})).await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+        assert_eq!(shard.session_id.as_deref(), Some("abc"));
+        assert_eq!(shard.next_event().await.unwrap().kind, "TYPING_START");
+        assert_eq!(shard.next_event().await.unwrap().kind, "RESUMED");
+        assert_eq!(shard.seq, Some(3));
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_invalid_session_resumable() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = start_session(&listener).await;
+            send(&mut ws, json!({"op": 9, "d": true})).await;
+
+            let resume = recv(&mut ws).await;
+            assert_eq!(resume["op"], 6);
+            assert_eq!(resume["d"]["session_id"], "abc");
+            send(&mut ws, json!({"op": 0, "s": 2, "t": "RESUMED", "d": {}})).await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+        assert_eq!(shard.next_event().await.unwrap().kind, "RESUMED");
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_invalid_session_not_resumable() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = start_session(&listener).await;
+            send(&mut ws, json!({"op": 9, "d": false})).await;
+
+            let identify = recv(&mut ws).await;
+            assert_eq!(identify["op"], 2);
+            send(
+                &mut ws,
+                json!({"op": 0, "s": 1, "t": "READY", "d": {"session_id": "def"}}),
+            )
+            .await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+        assert_eq!(shard.session_id.as_deref(), Some("def"));
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_close_resume() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = start_session(&listener).await;
+            close(&mut ws, close_codes::UNKNOWN_ERROR).await;
+
+            let mut ws = accept(&listener).await;
+            hello(&mut ws, 45000).await;
+            let resume = recv(&mut ws).await;
+            assert_eq!(resume["op"], 6);
+            send(&mut ws, json!({"op": 0, "s": 2, "t": "RESUMED", "d": {}})).await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+        assert_eq!(shard.next_event().await.unwrap().kind, "RESUMED");
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_reconnect_retry() {
+        let (listener, url) = bind().await;
+
+        let server = task::spawn(async move {
+            let mut ws = start_session(&listener).await;
+            close(&mut ws, close_codes::UNKNOWN_ERROR).await;
+
+            // Fail the WebSocket handshake of the first reconnect attempts.
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                drop(stream);
+            }
+
+            let mut ws = accept(&listener).await;
+            hello(&mut ws, 45000).await;
+            let resume = recv(&mut ws).await;
+            assert_eq!(resume["op"], 6);
+            send(&mut ws, json!({"op": 0, "s": 2, "t": "RESUMED", "d": {}})).await;
+            ws
+        });
+
+        let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+        assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+        assert_eq!(shard.next_event().await.unwrap().kind, "RESUMED");
+
+        server.await;
+    }
+
+    #[async_std::test]
+    async fn test_close_new_session() {
+        for &code in &[close_codes::INVALID_SEQUENCE, close_codes::SESSION_TIMEOUT] {
+            let (listener, url) = bind().await;
+
+            let server = task::spawn(async move {
+                let mut ws = start_session(&listener).await;
+                close(&mut ws, code).await;
+
+                let mut ws = accept(&listener).await;
+                handshake(&mut ws, 45000).await;
+                send(&mut ws, json!({"op": 0, "s": 1, "t": "READY", "d": {}})).await;
+                ws
+            });
+
+            let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+            assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+            assert_eq!(shard.next_event().await.unwrap().kind, "READY");
+
+            server.await;
+        }
+    }
+
+    #[async_std::test]
+    async fn test_close_fatal() {
+        for &code in &[
+            close_codes::AUTHENTICATION_FAILED,
+            close_codes::INVALID_SHARD,
+            close_codes::SHARDING_REQUIRED,
+        ] {
+            let (listener, url) = bind().await;
+
+            let server = task::spawn(async move {
+                let mut ws = accept(&listener).await;
+                handshake(&mut ws, 45000).await;
+                close(&mut ws, code).await;
+                ws
+            });
+
+            let mut shard = Shard::new(&url, TOKEN, [0, 1], limiter()).await.unwrap();
+            let err = shard.next_event().await.unwrap_err();
+
+            match (code, err) {
+                (
+                    close_codes::AUTHENTICATION_FAILED,
+                    Error::GatewayError(GatewayError::AuthenticationFailed),
+                )
+                | (close_codes::INVALID_SHARD, Error::GatewayError(GatewayError::InvalidShard))
+                | (
+                    close_codes::SHARDING_REQUIRED,
+                    Error::GatewayError(GatewayError::ShardingRequired),
+                ) => {}
+                (code, err) => panic!("unexpected error for close code {}: {:?}", code, err),
+            }
+
+            server.await;
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use async_std::sync::Arc;
+
+use bytes::Bytes;
+use hyper::client::connect::Connect;
+use hyper::header::HeaderValue;
+use hyper::Uri;
+
+use crate::internal::prelude::*;
+
+use super::client::Http;
+use super::invalid_requests::{self, InvalidRequestCallback, InvalidRequests};
+use super::prelude::*;
+use super::ratelimit::{DefaultRatelimiter, Ratelimiter};
+
+/// A builder for an [`Http`] client with custom settings.
+///
+/// # Examples
+///
+/// Sending requests to a local mock server:
+///
+/// ```
+/// use hyper::client::HttpConnector;
+/// use strife::http::HttpBuilder;
+///
+/// # fn main() -> strife::Result<()> {
+/// let http = HttpBuilder::new()
+///     .token("my token")
+///     .base_url("http://localhost:8080/api/v6")
+///     .user_agent("MyBot (https://example.com, 1.0)")
+///     .connector(HttpConnector::new())
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Http`]: struct.Http.html
+#[derive(Clone, Default)]
+pub struct HttpBuilder {
+    token: Option<Bytes>,
+    base_url: Option<String>,
+    user_agent: Option<String>,
+    client: Option<Arc<dyn HyperClient>>,
+    ratelimiter: Option<Arc<dyn Ratelimiter>>,
+    invalid_request_threshold: Option<u32>,
+    invalid_request_callback: Option<InvalidRequestCallback>,
+    invalid_request_circuit_breaker: bool,
+}
+
+impl HttpBuilder {
+    /// Creates a builder for a client with the default settings, that sends
+    /// requests without an API token.
+    pub fn new() -> HttpBuilder {
+        HttpBuilder::default()
+    }
+
+    /// Sets the API token used to authorize requests.
+    ///
+    /// The `Bot ` prefix is added to the token if necessary.
+    pub fn token<S: AsRef<str>>(&mut self, token: S) -> &mut Self {
+        self.token = Some(bot_token(token.as_ref()));
+        self
+    }
+
+    /// Sets the base URL requests are sent to, in place of
+    /// `https://discordapp.com/api/v6`.
+    ///
+    /// The base URL is followed by the path of each route, eg.
+    /// `{base_url}/channels/{channel.id}`.
+    pub fn base_url<S: Into<String>>(&mut self, base_url: S) -> &mut Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets the `user-agent` header sent with every request.
+    ///
+    /// Discord requires the user agent of bots to be of the form
+    /// `DiscordBot ($url, $version)`.
+    pub fn user_agent<S: Into<String>>(&mut self, user_agent: S) -> &mut Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the hyper client used to send requests.
+    ///
+    /// By default a client with an HTTPS connector is used.
+    pub fn client<C>(&mut self, client: hyper::Client<C>) -> &mut Self
+    where
+        C: Connect + Clone + Send + Sync + 'static,
+    {
+        self.client = Some(Arc::new(client));
+        self
+    }
+
+    /// Sets the connector of the hyper client used to send requests.
+    pub fn connector<C>(&mut self, connector: C) -> &mut Self
+    where
+        C: Connect + Clone + Send + Sync + 'static,
+    {
+        self.client(hyper::Client::builder().build(connector))
+    }
+
+    /// Sets the ratelimiter requests are sent through.
+    ///
+    /// By default each client has its own [`DefaultRatelimiter`]. Clients using
+    /// the same token should share a ratelimiter.
+    ///
+    /// [`DefaultRatelimiter`]: struct.DefaultRatelimiter.html
+    pub fn ratelimiter(&mut self, ratelimiter: Arc<dyn Ratelimiter>) -> &mut Self {
+        self.ratelimiter = Some(ratelimiter);
+        self
+    }
+
+    /// Sets the number of invalid requests in 10 minutes after which a warning
+    /// is logged.
+    ///
+    /// Requests responded to with a 401, 403 or 429 status are invalid. Discord
+    /// temporarily bans IP addresses that send [`INVALID_REQUEST_LIMIT`]
+    /// invalid requests in 10 minutes. Defaults to 8000.
+    ///
+    /// The threshold must be greater than `0`, or [`build`] fails.
+    ///
+    /// [`build`]: #method.build
+    /// [`INVALID_REQUEST_LIMIT`]: ../constants/constant.INVALID_REQUEST_LIMIT.html
+    pub fn invalid_request_threshold(&mut self, threshold: u32) -> &mut Self {
+        self.invalid_request_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets a callback to be called with the number of invalid requests in the
+    /// last 10 minutes, when it reaches the invalid request threshold.
+    ///
+    /// The callback is called again if the number drops below the threshold and
+    /// reaches it again.
+    pub fn on_invalid_request_threshold<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: Fn(u32) + Send + Sync + 'static,
+    {
+        self.invalid_request_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets whether requests fail without being sent while the invalid request
+    /// threshold is reached.
+    ///
+    /// Requests fail with a [`TooManyInvalidRequests`] error until enough
+    /// invalid requests are older than 10 minutes. Requests to get the gateway
+    /// URL are always sent, so that shards can still connect. Defaults to
+    /// `false`.
+    ///
+    /// [`TooManyInvalidRequests`]: enum.HttpError.html#variant.TooManyInvalidRequests
+    pub fn invalid_request_circuit_breaker(&mut self, enabled: bool) -> &mut Self {
+        self.invalid_request_circuit_breaker = enabled;
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidUri`] error if the base URL is not a valid URI, an
+    /// [`InvalidHeader`] error if the user agent is not a valid header value,
+    /// or an [`InvalidRequestThreshold`] error if the invalid request
+    /// threshold is `0`.
+    ///
+    /// [`InvalidUri`]: enum.HttpError.html#variant.InvalidUri
+    /// [`InvalidHeader`]: enum.HttpError.html#variant.InvalidHeader
+    /// [`InvalidRequestThreshold`]: enum.HttpError.html#variant.InvalidRequestThreshold
+    pub fn build(&self) -> Result<Http> {
+        let mut config = RequestConfig {
+            token: self.token.clone(),
+            ..RequestConfig::default()
+        };
+
+        if let Some(base_url) = &self.base_url {
+            base_url.parse::<Uri>().map_err(HttpError::InvalidUri)?;
+            config.base_url = Some(base_url.clone());
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            let user_agent = Bytes::from(user_agent.clone());
+            config.user_agent = HeaderValue::from_maybe_shared(user_agent.clone())
+                .map_err(|_| HttpError::InvalidHeader(user_agent))?;
+        }
+
+        let client = match &self.client {
+            Some(client) => Arc::clone(client),
+            None => default_client(),
+        };
+
+        let ratelimiter = match &self.ratelimiter {
+            Some(ratelimiter) => Arc::clone(ratelimiter),
+            None => default_ratelimiter(),
+        };
+
+        let threshold = self
+            .invalid_request_threshold
+            .unwrap_or(invalid_requests::DEFAULT_THRESHOLD);
+        if threshold == 0 {
+            return Err(HttpError::InvalidRequestThreshold.into());
+        }
+
+        let invalid_requests = InvalidRequests::new(
+            threshold,
+            self.invalid_request_circuit_breaker,
+            self.invalid_request_callback.clone(),
+        );
+
+        Ok(Http::from_parts(
+            client,
+            ratelimiter,
+            invalid_requests,
+            config,
+        ))
+    }
+}
+
+/// Creates the default hyper client, with an HTTPS connector.
+pub(crate) fn default_client() -> Arc<dyn HyperClient> {
+    Arc::new(hyper::Client::builder().build(HttpsConnector::new()))
+}
+
+/// Creates the default ratelimiter.
+pub(crate) fn default_ratelimiter() -> Arc<dyn Ratelimiter> {
+    Arc::new(DefaultRatelimiter::new())
+}
+
+/// Formats an API token as a bot token.
+pub(crate) fn bot_token(token: &str) -> Bytes {
+    // Trim whitespace from token.
+    let token = token.trim();
+    // Add "Bot " prefix to token if necessary.
+    if token.starts_with("Bot ") {
+        Bytes::copy_from_slice(token.as_bytes())
+    } else {
+        Bytes::from(format!("Bot {}", token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bot_token() {
+        assert_eq!(bot_token("abc"), "Bot abc");
+        assert_eq!(bot_token(" Bot abc\n"), "Bot abc");
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        match HttpBuilder::new().base_url("not a url").build() {
+            Err(Error::HttpError(HttpError::InvalidUri(_))) => {}
+            _ => panic!("expected invalid URI error"),
+        }
+
+        match HttpBuilder::new().user_agent("line\nbreak").build() {
+            Err(Error::HttpError(HttpError::InvalidHeader(_))) => {}
+            _ => panic!("expected invalid header error"),
+        }
+
+        match HttpBuilder::new().invalid_request_threshold(0).build() {
+            Err(Error::HttpError(HttpError::InvalidRequestThreshold)) => {}
+            _ => panic!("expected invalid request threshold error"),
+        }
+    }
+}
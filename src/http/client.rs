@@ -1,40 +1,122 @@
+use std::time::Instant;
+
 use async_std::sync::Arc;
 
-use bytes::Bytes;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
+use crate::builder::{
+    CreateInvite, CreateMessage, EditChannel, EditMember, EditMessage, EditRole, ExecuteWebhook,
+    ValidationError,
+};
+use crate::constants::BAN_DELETE_MESSAGE_DAYS_MAX;
 use crate::internal::prelude::*;
+use crate::model::channel::permissions::{OverwriteId, PermissionOverwrite};
+use crate::model::channel::{Channel, DMChannel, GuildChannel, Message};
+use crate::model::gateway::{BotGatewayInfo, GatewayInfo};
+use crate::model::guild::{
+    AuditLog, AuditLogEvent, Ban, Emoji, Guild, GuildEmbed, Integration, Member, PartialEmoji,
+    PartialGuild, Role,
+};
+use crate::model::id::*;
+use crate::model::invite::{Invite, InviteMetadata};
+use crate::model::user::{ClientUser, User};
+use crate::model::voice::VoiceRegion;
+use crate::model::webhook::Webhook;
 
+use super::builder::{bot_token, default_client, default_ratelimiter, HttpBuilder};
 use super::error::ErrorResponse;
+use super::invalid_requests::InvalidRequests;
 use super::prelude::*;
-use super::ratelimit::RateLimiter;
+use super::ratelimit::{self, Ratelimiter};
 
 /// An HTTP client for performing requests to the REST API.
+///
+/// Methods that take a `reason` show it in the audit log entry for the action.
+/// The reason can be at most 512 characters long.
+///
+/// A client with a custom base URL, user agent or connector can be created
+/// with an [`HttpBuilder`].
+///
+/// [`HttpBuilder`]: struct.HttpBuilder.html
 pub struct Http {
+    /// Settings applied to every request.
+    config: RequestConfig,
+    /// The hyper client requests are sent with.
+    client: Arc<dyn HyperClient>,
     /// Internal rate limit manager.
-    ratelimiter: RateLimiter,
+    ratelimiter: Arc<dyn Ratelimiter>,
+    /// Tracks invalid requests, to avoid a temporary ban.
+    invalid_requests: InvalidRequests,
 }
 
 impl Http {
     /// Creates a new HTTP client with the given API token.
     pub fn new<S: AsRef<str>>(token: S) -> Http {
-        // Trim whitespace from token.
-        let token = token.as_ref().trim();
-        // Add "Bot " prefix to token if necessary.
-        let token = if token.starts_with("Bot ") {
-            Bytes::copy_from_slice(token.as_bytes())
-        } else {
-            Bytes::from(format!("Bot {}", token))
+        let config = RequestConfig {
+            token: Some(bot_token(token.as_ref())),
+            ..RequestConfig::default()
         };
 
-        let client = hyper::Client::builder().build(HttpsConnector::new());
-        let client = Arc::new(client);
+        Http::from_parts(
+            default_client(),
+            default_ratelimiter(),
+            InvalidRequests::default(),
+            config,
+        )
+    }
+
+    /// Creates a builder for a client with custom settings.
+    pub fn builder() -> HttpBuilder {
+        HttpBuilder::new()
+    }
+
+    /// Creates a new HTTP client that sends requests without an API token.
+    ///
+    /// Only routes authenticated by a token in the URL, such as executing a
+    /// webhook, can be used by the client.
+    pub(crate) fn without_token() -> Http {
+        Http::from_parts(
+            default_client(),
+            default_ratelimiter(),
+            InvalidRequests::default(),
+            RequestConfig::default(),
+        )
+    }
 
+    pub(crate) fn from_parts(
+        client: Arc<dyn HyperClient>,
+        ratelimiter: Arc<dyn Ratelimiter>,
+        invalid_requests: InvalidRequests,
+        config: RequestConfig,
+    ) -> Http {
         Http {
-            ratelimiter: RateLimiter::new(client, token),
+            config,
+            client,
+            ratelimiter,
+            invalid_requests,
         }
     }
 
+    /// Returns the API token requests are authorized with, including the
+    /// `Bot ` prefix.
+    pub(crate) fn token(&self) -> Option<&str> {
+        let token = self.config.token.as_ref()?;
+        std::str::from_utf8(token).ok()
+    }
+
+    /// Returns the number of invalid requests sent in the last 10 minutes.
+    ///
+    /// Requests responded to with a 401, 403 or 429 status are invalid. Discord
+    /// temporarily bans IP addresses that send [`INVALID_REQUEST_LIMIT`]
+    /// invalid requests in 10 minutes.
+    ///
+    /// [`INVALID_REQUEST_LIMIT`]: ../constants/constant.INVALID_REQUEST_LIMIT.html
+    pub fn invalid_request_count(&self) -> u32 {
+        self.invalid_requests.count(Instant::now())
+    }
+
     /// Performs a request with rate limiting if necessary.
     ///
     /// # Stability
@@ -44,8 +126,38 @@ impl Http {
         json_body(&mut self.inner_request(req).await?).await
     }
 
+    /// Performs a request with rate limiting if necessary, ignoring the body of
+    /// the response.
+    ///
+    /// This should be used for routes that respond with `204 No Content`.
+    ///
+    /// # Stability
+    ///
+    /// This is not part of the stable API and may change at any time.
+    pub async fn request_empty(&self, req: Request<'_>) -> Result<()> {
+        self.inner_request(req).await?;
+        Ok(())
+    }
+
+    /// Performs a request with rate limiting if necessary, returning the
+    /// status, headers and raw body of the response.
+    ///
+    /// # Stability
+    ///
+    /// This is not part of the stable API and may change at any time.
+    pub async fn request_raw(&self, req: Request<'_>) -> Result<Response> {
+        Response::from_http(self.inner_request(req).await?).await
+    }
+
     async fn inner_request(&self, request: Request<'_>) -> Result<HttpResponse> {
-        let mut response = self.ratelimiter.perform(&request).await?;
+        let mut response = ratelimit::perform(
+            &*self.client,
+            &*self.ratelimiter,
+            &self.invalid_requests,
+            &self.config,
+            &request,
+        )
+        .await?;
 
         if response.status().is_success() {
             Ok(response)
@@ -53,7 +165,7 @@ impl Http {
             Err(Error::HttpError(HttpError::UnsuccessfulRequest(
                 ErrorResponse {
                     status: response.status(),
-                    url: request.route.url().to_string(),
+                    url: self.config.url(&request.route).into_owned(),
                     error: json_body(&mut response).await.ok(),
                 },
             )))
@@ -61,6 +173,1179 @@ impl Http {
     }
 }
 
+impl Http {
+    // Channels
+
+    /// Gets a channel.
+    pub async fn get_channel(&self, channel_id: ChannelId) -> Result<Channel> {
+        self.request(Request::new(Route::GetChannel { channel_id }))
+            .await
+    }
+
+    /// Edits the settings of a channel.
+    ///
+    /// The edit is validated before it is sent.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use strife::builder::EditChannel;
+    /// use strife::http::Http;
+    /// use strife::model::id::ChannelId;
+    ///
+    /// # async fn run() -> strife::Result<()> {
+    /// let http = Http::new("my token");
+    ///
+    /// let mut channel = EditChannel::new();
+    /// channel.name("general").topic("General chat");
+    /// http.edit_channel(ChannelId::from(1234), &channel, None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn edit_channel(
+        &self,
+        channel_id: ChannelId,
+        channel: &EditChannel,
+        reason: Option<&str>,
+    ) -> Result<Channel> {
+        channel.validate()?;
+
+        let request = json_request(Route::EditChannel { channel_id }, channel)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Deletes a guild channel, or closes a private channel.
+    pub async fn delete_channel(
+        &self,
+        channel_id: ChannelId,
+        reason: Option<&str>,
+    ) -> Result<Channel> {
+        let request = Request::new(Route::DeleteChannel { channel_id });
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Creates or replaces the permission overwrite of a user or role in a
+    /// channel.
+    pub async fn edit_channel_permission(
+        &self,
+        channel_id: ChannelId,
+        overwrite: &PermissionOverwrite,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let route = Route::EditChannelPermission {
+            channel_id,
+            overwrite_id: overwrite.id,
+        };
+        let request = json_request(route, overwrite)?;
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Deletes the permission overwrite of a user or role in a channel.
+    pub async fn delete_channel_permission(
+        &self,
+        channel_id: ChannelId,
+        overwrite_id: OverwriteId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::DeleteChannelPermission {
+            channel_id,
+            overwrite_id,
+        });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Triggers the typing indicator for the client user in a channel.
+    pub async fn broadcast_typing(&self, channel_id: ChannelId) -> Result<()> {
+        self.request_empty(Request::new(Route::BroadcastTyping { channel_id }))
+            .await
+    }
+
+    /// Adds a recipient to a group channel.
+    ///
+    /// The body is a JSON object with the fields `access_token`, an OAuth2
+    /// access token of the user with the `gdm.join` scope, and `nick`, the
+    /// nickname of the user.
+    pub async fn add_group_recipient<B>(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+        body: &B,
+    ) -> Result<()>
+    where
+        B: ?Sized + Serialize,
+    {
+        let route = Route::AddGroupRecipient {
+            channel_id,
+            user_id,
+        };
+        self.request_empty(json_request(route, body)?).await
+    }
+
+    /// Removes a recipient from a group channel.
+    pub async fn remove_group_recipient(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::RemoveGroupRecipient {
+            channel_id,
+            user_id,
+        }))
+        .await
+    }
+
+    // Messages
+
+    /// Gets the messages in a channel.
+    ///
+    /// The `limit` defaults to 50 messages, and must be between 1 and 100.
+    pub async fn get_messages(
+        &self,
+        channel_id: ChannelId,
+        around: Option<AroundMessage>,
+        limit: Option<u8>,
+    ) -> Result<Vec<Message>> {
+        self.request(Request::new(Route::GetMessages {
+            channel_id,
+            around,
+            limit,
+        }))
+        .await
+    }
+
+    /// Gets a message in a channel.
+    pub async fn get_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<Message> {
+        self.request(Request::new(Route::GetMessage {
+            channel_id,
+            message_id,
+        }))
+        .await
+    }
+
+    /// Sends a message to a channel.
+    ///
+    /// The message is validated before it is sent.
+    pub async fn create_message(
+        &self,
+        channel_id: ChannelId,
+        message: &CreateMessage,
+    ) -> Result<Message> {
+        message.validate()?;
+        self.request(json_request(Route::CreateMessage { channel_id }, message)?)
+            .await
+    }
+
+    /// Sends a message with attached files to a channel.
+    ///
+    /// The message is validated before it is sent, and can be empty if any
+    /// files are attached.
+    pub async fn create_message_with_files(
+        &self,
+        channel_id: ChannelId,
+        message: &CreateMessage,
+        files: Vec<FileUpload>,
+    ) -> Result<Message> {
+        message.validate_with_files(!files.is_empty())?;
+        let route = Route::CreateMessage { channel_id };
+        self.request(multipart_request(route, message, files)?)
+            .await
+    }
+
+    /// Edits a message sent by the client user.
+    ///
+    /// The message is validated before it is sent.
+    pub async fn edit_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        message: &EditMessage,
+    ) -> Result<Message> {
+        message.validate()?;
+        let route = Route::EditMessage {
+            channel_id,
+            message_id,
+        };
+        self.request(json_request(route, message)?).await
+    }
+
+    /// Deletes a message.
+    pub async fn delete_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::DeleteMessage {
+            channel_id,
+            message_id,
+        });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Deletes between 2 and 100 messages at once.
+    ///
+    /// Messages older than 2 weeks cannot be bulk deleted.
+    pub async fn delete_messages(
+        &self,
+        channel_id: ChannelId,
+        message_ids: &[MessageId],
+    ) -> Result<()> {
+        let body = json!({ "messages": message_ids });
+        self.request_empty(json_request(
+            Route::DeleteMessagesBulk { channel_id },
+            &body,
+        )?)
+        .await
+    }
+
+    /// Gets the pinned messages in a channel.
+    pub async fn get_pins(&self, channel_id: ChannelId) -> Result<Vec<Message>> {
+        self.request(Request::new(Route::GetPins { channel_id }))
+            .await
+    }
+
+    /// Pins a message in a channel.
+    pub async fn pin_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()> {
+        self.request_empty(Request::new(Route::PinMessage {
+            channel_id,
+            message_id,
+        }))
+        .await
+    }
+
+    /// Unpins a message in a channel.
+    pub async fn unpin_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()> {
+        self.request_empty(Request::new(Route::UnpinMessage {
+            channel_id,
+            message_id,
+        }))
+        .await
+    }
+
+    // Reactions
+
+    /// Reacts to a message as the client user.
+    pub async fn create_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &PartialEmoji,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::CreateReaction {
+            channel_id,
+            message_id,
+            emoji,
+        }))
+        .await
+    }
+
+    /// Deletes a reaction of the client user from a message.
+    pub async fn delete_own_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &PartialEmoji,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::DeleteOwnReaction {
+            channel_id,
+            message_id,
+            emoji,
+        }))
+        .await
+    }
+
+    /// Deletes a reaction of a user from a message.
+    pub async fn delete_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &PartialEmoji,
+        user_id: UserId,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::DeleteReaction {
+            channel_id,
+            message_id,
+            emoji,
+            user_id,
+        }))
+        .await
+    }
+
+    /// Deletes all reactions from a message.
+    pub async fn delete_reactions(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::DeleteReactions {
+            channel_id,
+            message_id,
+        }))
+        .await
+    }
+
+    /// Gets the users that reacted to a message with an emoji.
+    ///
+    /// The `limit` defaults to 25 users, and must be between 1 and 100.
+    pub async fn get_reaction_users(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &PartialEmoji,
+        before: Option<UserId>,
+        after: Option<UserId>,
+        limit: Option<u8>,
+    ) -> Result<Vec<User>> {
+        self.request(Request::new(Route::GetReactionUsers {
+            channel_id,
+            message_id,
+            emoji,
+            before,
+            after,
+            limit,
+        }))
+        .await
+    }
+
+    // Emojis
+
+    /// Gets the emojis in a guild.
+    pub async fn get_guild_emojis(&self, guild_id: GuildId) -> Result<Vec<Emoji>> {
+        self.request(Request::new(Route::GetGuildEmojis { guild_id }))
+            .await
+    }
+
+    /// Gets an emoji in a guild.
+    pub async fn get_emoji(&self, guild_id: GuildId, emoji_id: EmojiId) -> Result<Emoji> {
+        self.request(Request::new(Route::GetEmoji { guild_id, emoji_id }))
+            .await
+    }
+
+    /// Creates an emoji in a guild.
+    ///
+    /// The body is a JSON object with the fields `name`, `image`, the emoji
+    /// image as a base64 encoded data URI, and `roles`, the IDs of the roles
+    /// that can use the emoji.
+    pub async fn create_emoji<B>(
+        &self,
+        guild_id: GuildId,
+        body: &B,
+        reason: Option<&str>,
+    ) -> Result<Emoji>
+    where
+        B: ?Sized + Serialize,
+    {
+        let request = json_request(Route::CreateEmoji { guild_id }, body)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Edits an emoji in a guild.
+    ///
+    /// The body is a JSON object with any of the fields `name` and `roles`.
+    pub async fn edit_emoji<B>(
+        &self,
+        guild_id: GuildId,
+        emoji_id: EmojiId,
+        body: &B,
+        reason: Option<&str>,
+    ) -> Result<Emoji>
+    where
+        B: ?Sized + Serialize,
+    {
+        let request = json_request(Route::EditEmoji { guild_id, emoji_id }, body)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Deletes an emoji from a guild.
+    pub async fn delete_emoji(
+        &self,
+        guild_id: GuildId,
+        emoji_id: EmojiId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::DeleteEmoji { guild_id, emoji_id });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    // Guilds
+
+    /// Creates a guild.
+    ///
+    /// This can only be used by bots in less than 10 guilds.
+    ///
+    /// The body is a JSON object with the field `name`, and optionally
+    /// `region`, `icon`, `verification_level`, `default_message_notifications`,
+    /// `explicit_content_filter`, `roles` and `channels`.
+    pub async fn create_guild<B>(&self, body: &B) -> Result<Guild>
+    where
+        B: ?Sized + Serialize,
+    {
+        self.request(json_request(Route::CreateGuild, body)?).await
+    }
+
+    /// Gets a guild.
+    pub async fn get_guild(&self, guild_id: GuildId) -> Result<Guild> {
+        self.request(Request::new(Route::GetGuild { guild_id }))
+            .await
+    }
+
+    /// Edits the settings of a guild.
+    ///
+    /// The body is a JSON object with any of the fields `name`, `region`,
+    /// `verification_level`, `default_message_notifications`,
+    /// `explicit_content_filter`, `afk_channel_id`, `afk_timeout`, `icon`,
+    /// `owner_id`, `splash`, `banner`, `system_channel_id`, `rules_channel_id`,
+    /// `public_updates_channel_id` and `preferred_locale`.
+    pub async fn edit_guild<B>(
+        &self,
+        guild_id: GuildId,
+        body: &B,
+        reason: Option<&str>,
+    ) -> Result<Guild>
+    where
+        B: ?Sized + Serialize,
+    {
+        let request = json_request(Route::EditGuild { guild_id }, body)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Deletes a guild, the client user must be the owner.
+    pub async fn delete_guild(&self, guild_id: GuildId) -> Result<()> {
+        self.request_empty(Request::new(Route::DeleteGuild { guild_id }))
+            .await
+    }
+
+    /// Gets the channels in a guild.
+    pub async fn get_channels(&self, guild_id: GuildId) -> Result<Vec<GuildChannel>> {
+        self.request(Request::new(Route::GetChannels { guild_id }))
+            .await
+    }
+
+    /// Creates a channel in a guild.
+    ///
+    /// The body is a JSON object with the field `name`, and optionally `type`,
+    /// `topic`, `bitrate`, `user_limit`, `rate_limit_per_user`, `position`,
+    /// `permission_overwrites`, `parent_id` and `nsfw`.
+    pub async fn create_channel<B>(
+        &self,
+        guild_id: GuildId,
+        body: &B,
+        reason: Option<&str>,
+    ) -> Result<GuildChannel>
+    where
+        B: ?Sized + Serialize,
+    {
+        let request = json_request(Route::CreateChannel { guild_id }, body)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Edits the positions of channels in a guild.
+    ///
+    /// The body is a JSON array of objects with the fields `id` and `position`
+    /// of each channel to move.
+    pub async fn edit_channel_positions<B>(&self, guild_id: GuildId, body: &B) -> Result<()>
+    where
+        B: ?Sized + Serialize,
+    {
+        self.request_empty(json_request(
+            Route::EditChannelPositions { guild_id },
+            body,
+        )?)
+        .await
+    }
+
+    /// Gets the voice regions available to a guild.
+    pub async fn get_guild_regions(&self, guild_id: GuildId) -> Result<Vec<VoiceRegion>> {
+        self.request(Request::new(Route::GetGuildRegions { guild_id }))
+            .await
+    }
+
+    /// Gets the embed widget settings of a guild.
+    pub async fn get_guild_embed(&self, guild_id: GuildId) -> Result<GuildEmbed> {
+        self.request(Request::new(Route::GetGuildEmbed { guild_id }))
+            .await
+    }
+
+    /// Edits the embed widget settings of a guild.
+    ///
+    /// The body is a JSON object with any of the fields `enabled` and
+    /// `channel_id`.
+    pub async fn edit_guild_embed<B>(&self, guild_id: GuildId, body: &B) -> Result<GuildEmbed>
+    where
+        B: ?Sized + Serialize,
+    {
+        self.request(json_request(Route::EditGuildEmbed { guild_id }, body)?)
+            .await
+    }
+
+    /// Gets the vanity URL code of a guild, if one is set.
+    pub async fn get_guild_vanity_url(&self, guild_id: GuildId) -> Result<Option<String>> {
+        #[derive(Deserialize)]
+        struct VanityUrl {
+            code: Option<String>,
+        }
+
+        let vanity: VanityUrl = self
+            .request(Request::new(Route::GetGuildVanityUrl { guild_id }))
+            .await?;
+        Ok(vanity.code)
+    }
+
+    // Audit logs
+
+    /// Gets the audit log of a guild.
+    ///
+    /// The entries can be filtered by the user who made the changes, the type
+    /// of action, and paginated with the ID of the entry to get entries
+    /// before. The `limit` defaults to 50 entries, and must be between 1 and
+    /// 100.
+    pub async fn get_audit_logs(
+        &self,
+        guild_id: GuildId,
+        user_id: Option<UserId>,
+        action_type: Option<AuditLogEvent>,
+        before: Option<AuditLogEntryId>,
+        limit: Option<u8>,
+    ) -> Result<AuditLog> {
+        self.request(Request::new(Route::GetAuditLogs {
+            guild_id,
+            user_id,
+            action_type,
+            before,
+            limit,
+        }))
+        .await
+    }
+
+    // Members
+
+    /// Gets a member of a guild.
+    pub async fn get_member(&self, guild_id: GuildId, user_id: UserId) -> Result<Member> {
+        self.request(Request::new(Route::GetMember { guild_id, user_id }))
+            .await
+    }
+
+    /// Gets the members of a guild.
+    ///
+    /// The `limit` defaults to 1 member, and must be between 1 and 1000.
+    pub async fn get_guild_members(
+        &self,
+        guild_id: GuildId,
+        limit: Option<u16>,
+        after: Option<UserId>,
+    ) -> Result<Vec<Member>> {
+        self.request(Request::new(Route::GetGuildMembers {
+            guild_id,
+            limit,
+            after,
+        }))
+        .await
+    }
+
+    /// Edits a member of a guild.
+    ///
+    /// The edit is validated before it is sent.
+    pub async fn edit_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        member: &EditMember,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        member.validate()?;
+
+        let request = json_request(Route::EditMember { guild_id, user_id }, member)?;
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Edits the nickname of the client user in a guild, returning the new
+    /// nickname.
+    pub async fn edit_nickname(
+        &self,
+        guild_id: GuildId,
+        nick: Option<&str>,
+    ) -> Result<Option<String>> {
+        #[derive(Deserialize)]
+        struct Nickname {
+            nick: Option<String>,
+        }
+
+        let body = json!({ "nick": nick });
+        let nickname: Nickname = self
+            .request(json_request(Route::EditNickname { guild_id }, &body)?)
+            .await?;
+        Ok(nickname.nick)
+    }
+
+    /// Adds a role to a member of a guild.
+    pub async fn add_member_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::AddMemberRole {
+            guild_id,
+            user_id,
+            role_id,
+        });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Removes a role from a member of a guild.
+    pub async fn remove_member_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::RemoveMemberRole {
+            guild_id,
+            user_id,
+            role_id,
+        });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Kicks a member from a guild.
+    pub async fn kick_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::KickMember { guild_id, user_id });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    // Bans
+
+    /// Gets the bans in a guild.
+    pub async fn get_bans(&self, guild_id: GuildId) -> Result<Vec<Ban>> {
+        self.request(Request::new(Route::GetBans { guild_id }))
+            .await
+    }
+
+    /// Gets the ban of a user in a guild.
+    pub async fn get_ban(&self, guild_id: GuildId, user_id: UserId) -> Result<Ban> {
+        self.request(Request::new(Route::GetBan { guild_id, user_id }))
+            .await
+    }
+
+    /// Bans a user from a guild, optionally deleting the messages they sent in
+    /// the last 0-7 days.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if `delete_message_days` is greater than
+    /// [`BAN_DELETE_MESSAGE_DAYS_MAX`].
+    ///
+    /// [`ValidationError`]: ../builder/enum.ValidationError.html
+    /// [`BAN_DELETE_MESSAGE_DAYS_MAX`]: ../constants/constant.BAN_DELETE_MESSAGE_DAYS_MAX.html
+    pub async fn ban_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        delete_message_days: Option<u8>,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        if let Some(days) = delete_message_days {
+            let days = u64::from(days);
+            if days > BAN_DELETE_MESSAGE_DAYS_MAX {
+                return Err(ValidationError::ValueTooHigh {
+                    field: "delete message days",
+                    value: days,
+                    max: BAN_DELETE_MESSAGE_DAYS_MAX,
+                }
+                .into());
+            }
+        }
+
+        let request = Request::new(Route::BanMember {
+            guild_id,
+            user_id,
+            delete_message_days,
+        });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Removes the ban of a user from a guild.
+    pub async fn unban_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::UnbanMember { guild_id, user_id });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    // Roles
+
+    /// Gets the roles in a guild.
+    pub async fn get_roles(&self, guild_id: GuildId) -> Result<Vec<Role>> {
+        self.request(Request::new(Route::GetRoles { guild_id }))
+            .await
+    }
+
+    /// Creates a role in a guild.
+    ///
+    /// The role is validated before it is created.
+    pub async fn create_role(
+        &self,
+        guild_id: GuildId,
+        role: &EditRole,
+        reason: Option<&str>,
+    ) -> Result<Role> {
+        role.validate()?;
+
+        let request = json_request(Route::CreateRole { guild_id }, role)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Edits a role in a guild.
+    ///
+    /// The edit is validated before it is sent.
+    pub async fn edit_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        role: &EditRole,
+        reason: Option<&str>,
+    ) -> Result<Role> {
+        role.validate()?;
+
+        let request = json_request(Route::EditRole { guild_id, role_id }, role)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Edits the positions of roles in a guild, returning all roles in the
+    /// guild.
+    ///
+    /// The body is a JSON array of objects with the fields `id` and `position`
+    /// of each role to move.
+    pub async fn edit_role_positions<B>(&self, guild_id: GuildId, body: &B) -> Result<Vec<Role>>
+    where
+        B: ?Sized + Serialize,
+    {
+        self.request(json_request(Route::EditRolePositions { guild_id }, body)?)
+            .await
+    }
+
+    /// Deletes a role from a guild.
+    pub async fn delete_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let request = Request::new(Route::DeleteRole { guild_id, role_id });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    // Prune
+
+    /// Gets the number of members that would be removed in a prune of members
+    /// inactive for the given number of days (defaults to 7).
+    pub async fn get_guild_prune_count(&self, guild_id: GuildId, days: Option<u64>) -> Result<u64> {
+        let prune: PruneCount = self
+            .request(Request::new(Route::GetGuildPruneCount { guild_id, days }))
+            .await?;
+        Ok(prune.pruned.unwrap_or_default())
+    }
+
+    /// Removes members inactive for the given number of days (defaults to 7)
+    /// from a guild.
+    ///
+    /// The number of members removed is returned if `compute_prune_count` is
+    /// `true`, which is discouraged for large guilds.
+    pub async fn prune_guild_members(
+        &self,
+        guild_id: GuildId,
+        days: Option<u64>,
+        compute_prune_count: Option<bool>,
+        reason: Option<&str>,
+    ) -> Result<Option<u64>> {
+        let request = Request::new(Route::PruneGuildMembers {
+            guild_id,
+            days,
+            compute_prune_count,
+        });
+        let prune: PruneCount = self.request(with_reason(request, reason)?).await?;
+        Ok(prune.pruned)
+    }
+
+    // Integrations
+
+    /// Gets the integrations of a guild.
+    pub async fn get_guild_integrations(&self, guild_id: GuildId) -> Result<Vec<Integration>> {
+        self.request(Request::new(Route::GetGuildIntegrations { guild_id }))
+            .await
+    }
+
+    /// Attaches an integration to a guild.
+    ///
+    /// The body is a JSON object with the fields `type` and `id` of the
+    /// integration.
+    pub async fn create_integration<B>(&self, guild_id: GuildId, body: &B) -> Result<()>
+    where
+        B: ?Sized + Serialize,
+    {
+        self.request_empty(json_request(Route::CreateIntegration { guild_id }, body)?)
+            .await
+    }
+
+    /// Edits the behavior and settings of an integration in a guild.
+    ///
+    /// The body is a JSON object with the fields `expire_behavior`,
+    /// `expire_grace_period` and `enable_emoticons`.
+    pub async fn edit_integration<B>(
+        &self,
+        guild_id: GuildId,
+        integration_id: IntegrationId,
+        body: &B,
+    ) -> Result<()>
+    where
+        B: ?Sized + Serialize,
+    {
+        let route = Route::EditIntegration {
+            guild_id,
+            integration_id,
+        };
+        self.request_empty(json_request(route, body)?).await
+    }
+
+    /// Deletes an integration from a guild.
+    pub async fn delete_integration(
+        &self,
+        guild_id: GuildId,
+        integration_id: IntegrationId,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::DeleteIntegration {
+            guild_id,
+            integration_id,
+        }))
+        .await
+    }
+
+    /// Syncs an integration in a guild.
+    pub async fn sync_integration(
+        &self,
+        guild_id: GuildId,
+        integration_id: IntegrationId,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::SyncIntegration {
+            guild_id,
+            integration_id,
+        }))
+        .await
+    }
+
+    // Invites
+
+    /// Gets an invite by its code.
+    ///
+    /// If `with_counts` is `true` the invite includes the approximate number
+    /// of members in the guild.
+    pub async fn get_invite(&self, code: &str, with_counts: bool) -> Result<Invite> {
+        self.request(Request::new(Route::GetInvite {
+            code,
+            with_counts: Some(with_counts),
+        }))
+        .await
+    }
+
+    /// Deletes an invite, returning the deleted invite.
+    pub async fn delete_invite(&self, code: &str, reason: Option<&str>) -> Result<Invite> {
+        let request = Request::new(Route::DeleteInvite { code });
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Gets the invites in a guild.
+    pub async fn get_guild_invites(&self, guild_id: GuildId) -> Result<Vec<InviteMetadata>> {
+        self.request(Request::new(Route::GetGuildInvites { guild_id }))
+            .await
+    }
+
+    /// Gets the invites for a channel.
+    pub async fn get_channel_invites(&self, channel_id: ChannelId) -> Result<Vec<InviteMetadata>> {
+        self.request(Request::new(Route::GetInvites { channel_id }))
+            .await
+    }
+
+    /// Creates an invite for a channel.
+    ///
+    /// The invite is validated before it is created.
+    pub async fn create_invite(
+        &self,
+        channel_id: ChannelId,
+        invite: &CreateInvite,
+        reason: Option<&str>,
+    ) -> Result<InviteMetadata> {
+        invite.validate()?;
+
+        let request = json_request(Route::CreateInvite { channel_id }, invite)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    // Webhooks
+
+    /// Creates a webhook for a channel.
+    ///
+    /// The body is a JSON object with the field `name`, and optionally
+    /// `avatar`, the avatar image as a base64 encoded data URI.
+    pub async fn create_webhook<B>(
+        &self,
+        channel_id: ChannelId,
+        body: &B,
+        reason: Option<&str>,
+    ) -> Result<Webhook>
+    where
+        B: ?Sized + Serialize,
+    {
+        let request = json_request(Route::CreateChannelWebhook { channel_id }, body)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Gets the webhooks for a channel.
+    pub async fn get_channel_webhooks(&self, channel_id: ChannelId) -> Result<Vec<Webhook>> {
+        self.request(Request::new(Route::GetChannelWebhooks { channel_id }))
+            .await
+    }
+
+    /// Gets the webhooks in a guild.
+    pub async fn get_guild_webhooks(&self, guild_id: GuildId) -> Result<Vec<Webhook>> {
+        self.request(Request::new(Route::GetGuildWebhooks { guild_id }))
+            .await
+    }
+
+    /// Gets a webhook.
+    pub async fn get_webhook(&self, webhook_id: WebhookId) -> Result<Webhook> {
+        self.request(Request::new(Route::GetWebhook { webhook_id }))
+            .await
+    }
+
+    /// Gets a webhook with its token, the returned webhook has no user.
+    ///
+    /// This does not require authentication.
+    pub async fn get_webhook_with_token(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+    ) -> Result<Webhook> {
+        self.request(Request::new(Route::GetWebhookWithToken {
+            webhook_id,
+            token,
+        }))
+        .await
+    }
+
+    /// Edits a webhook.
+    ///
+    /// The body is a JSON object with any of the fields `name`, `avatar` and
+    /// `channel_id`.
+    pub async fn edit_webhook<B>(
+        &self,
+        webhook_id: WebhookId,
+        body: &B,
+        reason: Option<&str>,
+    ) -> Result<Webhook>
+    where
+        B: ?Sized + Serialize,
+    {
+        let request = json_request(Route::EditWebhook { webhook_id }, body)?;
+        self.request(with_reason(request, reason)?).await
+    }
+
+    /// Edits a webhook with its token, the channel of the webhook cannot be
+    /// changed.
+    ///
+    /// This does not require authentication.
+    ///
+    /// The body is a JSON object with any of the fields `name` and `avatar`.
+    pub async fn edit_webhook_with_token<B>(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+        body: &B,
+    ) -> Result<Webhook>
+    where
+        B: ?Sized + Serialize,
+    {
+        let route = Route::EditWebhookWithToken { webhook_id, token };
+        self.request(json_request(route, body)?).await
+    }
+
+    /// Deletes a webhook.
+    pub async fn delete_webhook(&self, webhook_id: WebhookId, reason: Option<&str>) -> Result<()> {
+        let request = Request::new(Route::DeleteWebhook { webhook_id });
+        self.request_empty(with_reason(request, reason)?).await
+    }
+
+    /// Deletes a webhook with its token.
+    ///
+    /// This does not require authentication.
+    pub async fn delete_webhook_with_token(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+    ) -> Result<()> {
+        self.request_empty(Request::new(Route::DeleteWebhookWithToken {
+            webhook_id,
+            token,
+        }))
+        .await
+    }
+
+    /// Executes a webhook, optionally attaching files.
+    ///
+    /// The message is validated before it is sent, and can be empty if any
+    /// files are attached. If `wait` is `true` the
+    /// created message is returned, otherwise the request completes before the
+    /// message is created and `None` is returned.
+    ///
+    /// This does not require authentication.
+    pub async fn execute_webhook(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+        wait: bool,
+        message: &ExecuteWebhook,
+        files: Vec<FileUpload>,
+    ) -> Result<Option<Message>> {
+        message.validate_with_files(!files.is_empty())?;
+
+        let route = Route::ExecuteWebhook {
+            webhook_id,
+            token,
+            wait: Some(wait),
+        };
+        let request = if files.is_empty() {
+            json_request(route, message)?
+        } else {
+            multipart_request(route, message, files)?
+        };
+
+        let response = self.request_raw(request).await?;
+        if wait {
+            response.json().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Users
+
+    /// Gets the client user.
+    pub async fn get_current_user(&self) -> Result<ClientUser> {
+        self.request(Request::new(Route::GetCurrentUser)).await
+    }
+
+    /// Edits the client user.
+    ///
+    /// The body is a JSON object with any of the fields `username` and
+    /// `avatar`, the avatar image as a base64 encoded data URI.
+    pub async fn edit_current_user<B>(&self, body: &B) -> Result<ClientUser>
+    where
+        B: ?Sized + Serialize,
+    {
+        self.request(json_request(Route::EditCurrentUser, body)?)
+            .await
+    }
+
+    /// Gets a user.
+    pub async fn get_user(&self, user_id: UserId) -> Result<User> {
+        self.request(Request::new(Route::GetUser { user_id })).await
+    }
+
+    /// Gets the guilds the client user is in.
+    pub async fn get_current_user_guilds(&self) -> Result<Vec<PartialGuild>> {
+        self.request(Request::new(Route::GetCurrentUserGuilds))
+            .await
+    }
+
+    /// Leaves a guild.
+    pub async fn leave_guild(&self, guild_id: GuildId) -> Result<()> {
+        self.request_empty(Request::new(Route::LeaveGuild { guild_id }))
+            .await
+    }
+
+    /// Creates a direct message channel with a user.
+    pub async fn create_private_channel(&self, recipient_id: UserId) -> Result<DMChannel> {
+        let body = json!({ "recipient_id": recipient_id });
+        self.request(json_request(Route::CreatePrivateChannel, &body)?)
+            .await
+    }
+
+    // Voice
+
+    /// Gets the voice regions that can be used when creating guilds.
+    pub async fn get_voice_regions(&self) -> Result<Vec<VoiceRegion>> {
+        self.request(Request::new(Route::GetVoiceRegions)).await
+    }
+
+    // Gateway
+
+    /// Gets information on connecting to the gateway.
+    pub async fn get_gateway(&self) -> Result<GatewayInfo> {
+        self.request(Request::new(Route::GetGateway)).await
+    }
+
+    /// Gets information on connecting to the gateway as a bot user, including
+    /// the recommended number of shards and the session start limit.
+    pub async fn get_bot_gateway(&self) -> Result<BotGatewayInfo> {
+        self.request(Request::new(Route::GetBotGateway)).await
+    }
+}
+
+/// The response of the prune routes.
+#[derive(Deserialize)]
+struct PruneCount {
+    pruned: Option<u64>,
+}
+
+/// Creates a request with a JSON body.
+fn json_request<'a, B>(route: Route<'a>, body: &B) -> Result<Request<'a>>
+where
+    B: ?Sized + Serialize,
+{
+    let mut request = Request::new(route);
+    request.json(body)?;
+    Ok(request)
+}
+
+/// Adds an audit log reason to a request, if one is given.
+fn with_reason<'a>(mut request: Request<'a>, reason: Option<&str>) -> Result<Request<'a>> {
+    if let Some(reason) = reason {
+        request.reason(reason)?;
+    }
+    Ok(request)
+}
+
+/// Creates a request with a multipart body, containing a JSON payload and
+/// files.
+fn multipart_request<'a, B>(
+    route: Route<'a>,
+    body: &B,
+    files: Vec<FileUpload>,
+) -> Result<Request<'a>>
+where
+    B: ?Sized + Serialize,
+{
+    let mut form = Multipart::new();
+    form.payload_json(body)?.files(files);
+
+    let mut request = Request::new(route);
+    request.multipart(&form)?;
+    Ok(request)
+}
+
 async fn json_body<T: DeserializeOwned>(response: &mut HttpResponse) -> Result<T> {
     use bytes::buf::BufExt;
 
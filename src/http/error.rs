@@ -14,9 +14,19 @@ pub enum Error {
     /// An invalid header value.
     #[error("invalid header value")]
     InvalidHeader(Bytes),
+    /// The invalid request threshold was set to `0`.
+    #[error("invalid request threshold must be greater than zero")]
+    InvalidRequestThreshold,
     /// Attempted to parse an invalid URI.
     #[error(transparent)]
     InvalidUri(#[from] InvalidUri),
+    /// A client that requires an API token was created from an HTTP client
+    /// without one.
+    #[error("HTTP client has no API token")]
+    MissingToken,
+    /// Attempted to parse an invalid webhook URL.
+    #[error("invalid webhook URL: {0}")]
+    InvalidWebhookUrl(String),
     /// An error parsing a header.
     #[error("failed to parse header: {name}")]
     ParseHeaderError {
@@ -25,6 +35,13 @@ pub enum Error {
         /// The header value.
         value: Bytes,
     },
+    /// A request was not sent, because too many invalid requests have been
+    /// sent recently.
+    #[error("too many invalid requests: {count} in the last 10 minutes")]
+    TooManyInvalidRequests {
+        /// The number of invalid requests in the last 10 minutes.
+        count: u32,
+    },
     /// An unsuccessful request.
     #[error("request to \"{}\" failed: {}", .0.url, .0.status)]
     UnsuccessfulRequest(ErrorResponse),
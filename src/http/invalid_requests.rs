@@ -0,0 +1,226 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_std::sync::Arc;
+use hyper::StatusCode;
+
+use crate::constants::INVALID_REQUEST_LIMIT;
+use crate::internal::prelude::*;
+
+use super::prelude::*;
+
+/// The period over which Discord counts invalid requests.
+const INVALID_REQUEST_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// The default number of invalid requests in the period, after which a warning
+/// is logged.
+pub(crate) const DEFAULT_THRESHOLD: u32 = INVALID_REQUEST_LIMIT / 10 * 8;
+
+/// A callback called with the number of invalid requests in the last 10
+/// minutes, when it reaches the threshold.
+pub(crate) type InvalidRequestCallback = Arc<dyn Fn(u32) + Send + Sync>;
+
+/// Tracks the number of invalid requests sent in the last 10 minutes, to avoid
+/// the IP address being temporarily banned by Discord.
+///
+/// Responses with a 401, 403 or 429 status are counted as invalid requests.
+pub(crate) struct InvalidRequests {
+    /// The number of invalid requests after which a warning is logged.
+    threshold: u32,
+    /// Whether requests are rejected while the threshold is exceeded.
+    circuit_breaker: bool,
+    callback: Option<InvalidRequestCallback>,
+    state: Mutex<State>,
+}
+
+struct State {
+    /// When each invalid request in the period was responded to.
+    times: VecDeque<Instant>,
+    /// Whether the threshold was reached, and has not yet been dropped below.
+    exceeded: bool,
+}
+
+impl InvalidRequests {
+    pub fn new(
+        threshold: u32,
+        circuit_breaker: bool,
+        callback: Option<InvalidRequestCallback>,
+    ) -> InvalidRequests {
+        InvalidRequests {
+            threshold,
+            circuit_breaker,
+            callback,
+            state: Mutex::new(State {
+                times: VecDeque::new(),
+                exceeded: false,
+            }),
+        }
+    }
+
+    /// Returns the number of invalid requests in the last 10 minutes.
+    pub fn count(&self, now: Instant) -> u32 {
+        let mut state = self.state.lock().unwrap();
+        state.expire(now);
+        state.times.len() as u32
+    }
+
+    /// Checks whether a request can be sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TooManyInvalidRequests`] error if the circuit breaker is
+    /// enabled and the threshold has been reached.
+    ///
+    /// [`TooManyInvalidRequests`]: enum.HttpError.html#variant.TooManyInvalidRequests
+    pub fn check(&self, now: Instant) -> Result<()> {
+        if !self.circuit_breaker {
+            return Ok(());
+        }
+
+        let count = self.count(now);
+        if count >= self.threshold {
+            return Err(HttpError::TooManyInvalidRequests { count }.into());
+        }
+        Ok(())
+    }
+
+    /// Records the status of a response.
+    pub fn record(&self, status: StatusCode, now: Instant) {
+        if !is_invalid(status) {
+            return;
+        }
+
+        let count = {
+            let mut state = self.state.lock().unwrap();
+            state.expire(now);
+
+            // Warn again once the count has dropped below the threshold.
+            if (state.times.len() as u32) < self.threshold {
+                state.exceeded = false;
+            }
+            state.times.push_back(now);
+
+            let count = state.times.len() as u32;
+            if count < self.threshold || state.exceeded {
+                return;
+            }
+            state.exceeded = true;
+            count
+        };
+
+        log::warn!(
+            "{} invalid requests sent in the last 10 minutes, Discord bans IP addresses after {}",
+            count,
+            INVALID_REQUEST_LIMIT
+        );
+
+        if let Some(callback) = &self.callback {
+            callback(count);
+        }
+    }
+}
+
+impl Default for InvalidRequests {
+    fn default() -> Self {
+        InvalidRequests::new(DEFAULT_THRESHOLD, false, None)
+    }
+}
+
+impl State {
+    /// Removes invalid requests from before the period.
+    fn expire(&mut self, now: Instant) {
+        while let Some(&time) = self.times.front() {
+            if now.saturating_duration_since(time) < INVALID_REQUEST_PERIOD {
+                break;
+            }
+            self.times.pop_front();
+        }
+    }
+}
+
+/// Returns whether a response status counts as an invalid request.
+fn is_invalid(status: StatusCode) -> bool {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let invalid = InvalidRequests::default();
+        let start = Instant::now();
+
+        invalid.record(StatusCode::OK, start);
+        invalid.record(StatusCode::NOT_FOUND, start);
+        invalid.record(StatusCode::UNAUTHORIZED, start);
+        invalid.record(StatusCode::FORBIDDEN, start + Duration::from_secs(60));
+        invalid.record(
+            StatusCode::TOO_MANY_REQUESTS,
+            start + Duration::from_secs(120),
+        );
+        assert_eq!(invalid.count(start + Duration::from_secs(120)), 3);
+
+        // Invalid requests older than 10 minutes are not counted.
+        assert_eq!(invalid.count(start + INVALID_REQUEST_PERIOD), 2);
+        assert_eq!(
+            invalid.count(start + INVALID_REQUEST_PERIOD + Duration::from_secs(120)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_threshold_callback() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let callback = {
+            let calls = Arc::clone(&calls);
+            Arc::new(move |count| {
+                assert_eq!(count, 3);
+                calls.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let invalid = InvalidRequests::new(3, false, Some(callback));
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            invalid.record(StatusCode::FORBIDDEN, start);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // The callback is called again after the count drops below the
+        // threshold and reaches it again.
+        let later = start + INVALID_REQUEST_PERIOD;
+        for _ in 0..3 {
+            invalid.record(StatusCode::FORBIDDEN, later);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let start = Instant::now();
+
+        let invalid = InvalidRequests::new(2, false, None);
+        invalid.record(StatusCode::FORBIDDEN, start);
+        invalid.record(StatusCode::FORBIDDEN, start);
+        assert!(invalid.check(start).is_ok());
+
+        let invalid = InvalidRequests::new(2, true, None);
+        invalid.record(StatusCode::FORBIDDEN, start);
+        assert!(invalid.check(start).is_ok());
+        invalid.record(StatusCode::FORBIDDEN, start);
+        match invalid.check(start) {
+            Err(Error::HttpError(HttpError::TooManyInvalidRequests { count: 2 })) => {}
+            _ => panic!("expected too many invalid requests error"),
+        }
+
+        assert!(invalid.check(start + INVALID_REQUEST_PERIOD).is_ok());
+    }
+}
@@ -4,15 +4,24 @@
 //! Discord API endpoints are ratelimited to prevent spam. The library takes
 //! preventative measures to ensure that requests are not ratelimited.
 
+mod builder;
 mod client;
 mod error;
+mod invalid_requests;
 mod prelude;
 mod ratelimit;
 mod request;
+mod response;
 mod routing;
+mod webhook;
 
+pub use self::builder::HttpBuilder;
 pub use self::client::Http;
 pub use self::error::Error as HttpError;
+pub use self::ratelimit::{DefaultRatelimiter, RatelimitHeaders, Ratelimiter};
+pub use self::request::FileUpload;
+pub use self::routing::{AroundMessage, Bucket, Method};
+pub use self::webhook::WebhookClient;
 
 /// Unstable HTTP API.
 ///
@@ -23,6 +32,7 @@ pub use self::error::Error as HttpError;
 ///
 /// [`Http`]: struct.Http.html
 pub mod unstable {
-    pub use super::request::Request;
+    pub use super::request::{Multipart, Request};
+    pub use super::response::Response;
     pub use super::routing::Route;
 }
@@ -1,11 +1,34 @@
 //! Private re-exports of commonly used types in the http module.
 
+use std::future::Future;
+use std::pin::Pin;
+
+use hyper::client::connect::Connect;
+
 pub use super::error::Error as HttpError;
-pub use super::request::Request;
-pub use super::routing::{Bucket, Method, Route};
+pub(crate) use super::request::RequestConfig;
+pub use super::request::{FileUpload, Multipart, Request};
+pub use super::response::Response;
+pub use super::routing::{AroundMessage, Bucket, Method, Route};
 
 pub type HttpsConnector = hyper_tls::HttpsConnector<hyper::client::HttpConnector>;
-pub type HyperClient = hyper::Client<HttpsConnector>;
 
 pub type HttpRequest = hyper::Request<hyper::Body>;
 pub type HttpResponse = hyper::Response<hyper::Body>;
+
+pub type ResponseFuture = Pin<Box<dyn Future<Output = hyper::Result<HttpResponse>> + Send>>;
+
+/// A hyper client with the type of its connector erased.
+pub trait HyperClient: Send + Sync {
+    /// Sends a request.
+    fn request(&self, req: HttpRequest) -> ResponseFuture;
+}
+
+impl<C> HyperClient for hyper::Client<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    fn request(&self, req: HttpRequest) -> ResponseFuture {
+        Box::pin(hyper::Client::request(self, req))
+    }
+}
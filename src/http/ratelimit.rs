@@ -1,115 +1,514 @@
 use std::collections::HashMap;
+use std::mem::{self, Discriminant};
 use std::str::FromStr;
-use std::time::Duration;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
 use std::{i64, str};
 
 use async_std::sync::{Arc, Mutex, RwLock};
+use async_trait::async_trait;
 use bytes::Bytes;
 use futures_timer::Delay;
 use hyper::{HeaderMap, StatusCode};
 
 use crate::internal::prelude::*;
+use crate::model::snowflake::Snowflake;
 
+use super::invalid_requests::InvalidRequests;
 use super::prelude::*;
 
+const RATELIMIT_BUCKET: &str = "x-ratelimit-bucket";
 const RATELIMIT_GLOBAL: &str = "x-ratelimit-global";
 const RATELIMIT_LIMIT: &str = "x-ratelimit-limit";
 const RATELIMIT_REMAINING: &str = "x-ratelimit-remaining";
-
-#[cfg(any(test, feature = "systime_ratelimits"))]
 const RATELIMIT_RESET: &str = "x-ratelimit-reset";
-#[cfg(any(test, not(feature = "systime_ratelimits")))]
 const RATELIMIT_RESET_AFTER: &str = "x-ratelimit-reset-after";
 
 const RETRY_AFTER: &str = "retry-after";
 
-/// Ratelimiter for requests the the Discord REST API.
-pub struct RateLimiter {
-    token: Bytes,
-    client: Arc<HyperClient>,
-    global: Arc<Mutex<()>>,
-    routes: Arc<RwLock<HashMap<Bucket, Arc<Mutex<RateLimit>>>>>,
+/// The number of requests per second allowed by the global ratelimit, unless
+/// it has been raised for the bot.
+const GLOBAL_RATELIMIT: u32 = 50;
+
+/// How long a bucket must be unused after it resets before it is removed.
+const BUCKET_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+/// The minimum interval between removing expired buckets.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A ratelimiter for requests to the Discord REST API.
+///
+/// Before each request is sent [`acquire`] is called, which waits until the
+/// request can be sent without exceeding the ratelimits. Once a response is
+/// received, [`update`] is called with its ratelimit headers. If the request
+/// was ratelimited it is resent after [`update`] returns.
+///
+/// The [`DefaultRatelimiter`] keeps its state in memory, so is only suitable
+/// when a single process uses the token. Processes sharing a token can share
+/// their ratelimits by implementing this trait over a shared backend.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use strife::client::async_trait;
+/// use strife::http::{Bucket, Http, Method, RatelimitHeaders, Ratelimiter};
+///
+/// struct LoggingRatelimiter;
+///
+/// #[async_trait]
+/// impl Ratelimiter for LoggingRatelimiter {
+///     async fn acquire(&self, method: Method, bucket: Bucket) {
+///         println!("sending {:?} request in {:?}", method, bucket);
+///     }
+///
+///     async fn update(&self, _method: Method, bucket: Bucket, headers: &RatelimitHeaders) {
+///         println!("{:?} requests remaining in {:?}", headers.remaining, bucket);
+///     }
+/// }
+///
+/// # fn main() -> strife::Result<()> {
+/// let http = Http::builder()
+///     .token("my token")
+///     .ratelimiter(Arc::new(LoggingRatelimiter))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`acquire`]: #tymethod.acquire
+/// [`update`]: #tymethod.update
+/// [`DefaultRatelimiter`]: struct.DefaultRatelimiter.html
+#[async_trait]
+pub trait Ratelimiter: Send + Sync {
+    /// Waits until a request to a route in the bucket can be sent.
+    ///
+    /// Routes in the same bucket with different methods may have different
+    /// ratelimits.
+    async fn acquire(&self, method: Method, bucket: Bucket);
+
+    /// Updates the ratelimits of the bucket from the headers of a response to
+    /// a request with the method.
+    ///
+    /// This is not called for requests in [`Bucket::None`].
+    ///
+    /// [`Bucket::None`]: enum.Bucket.html#variant.None
+    async fn update(&self, method: Method, bucket: Bucket, headers: &RatelimitHeaders);
 }
 
-impl RateLimiter {
-    /// Creates a new rate limit manager.
-    pub fn new<T>(client: Arc<HyperClient>, token: T) -> RateLimiter
-    where
-        T: Into<Bytes>,
-    {
-        RateLimiter {
-            token: token.into(),
-            client,
-            global: Default::default(),
-            routes: Default::default(),
+/// The ratelimit headers of a response.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RatelimitHeaders {
+    /// The opaque hash of the ratelimit bucket, which may be shared between
+    /// routes.
+    pub bucket: Option<String>,
+    /// Whether the global ratelimit was exceeded.
+    pub global: bool,
+    /// The number of requests that can be made in the bucket.
+    pub limit: Option<u64>,
+    /// The number of requests remaining in the bucket.
+    pub remaining: Option<u64>,
+    /// The time at which the bucket resets, in seconds since the Unix epoch.
+    pub reset: Option<f64>,
+    /// The time in seconds until the bucket resets.
+    pub reset_after: Option<f64>,
+    /// The time in milliseconds to wait before retrying the request.
+    ///
+    /// This is only present if the request was ratelimited.
+    pub retry_after: Option<u64>,
+}
+
+impl RatelimitHeaders {
+    /// Parses the ratelimit headers from a map of response headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`HttpError`] if a ratelimit header has an invalid value.
+    ///
+    /// [`HttpError`]: enum.HttpError.html
+    pub fn from_headers(headers: &HeaderMap) -> Result<RatelimitHeaders> {
+        Ok(RatelimitHeaders {
+            bucket: parse_header(headers, RATELIMIT_BUCKET)?,
+            global: headers.contains_key(RATELIMIT_GLOBAL),
+            limit: parse_header(headers, RATELIMIT_LIMIT)?,
+            remaining: parse_header(headers, RATELIMIT_REMAINING)?,
+            reset: parse_header(headers, RATELIMIT_RESET)?,
+            reset_after: parse_header(headers, RATELIMIT_RESET_AFTER)?,
+            retry_after: parse_header(headers, RETRY_AFTER)?,
+        })
+    }
+}
+
+/// Performs a ratelimited request.
+pub(crate) async fn perform(
+    client: &dyn HyperClient,
+    ratelimiter: &dyn Ratelimiter,
+    invalid_requests: &InvalidRequests,
+    config: &RequestConfig,
+    request: &Request<'_>,
+) -> Result<HttpResponse> {
+    let method = request.route.method();
+    let bucket = request.route.bucket();
+
+    loop {
+        if !request.route.is_essential() {
+            invalid_requests.check(Instant::now())?;
+        }
+        let req = request.build(config)?;
+
+        ratelimiter.acquire(method, bucket).await;
+
+        let response = client.request(req).await.map_err(HttpError::HyperError)?;
+        invalid_requests.record(response.status(), Instant::now());
+
+        // No ratelimits apply to this request.
+        if bucket == Bucket::None {
+            return Ok(response);
+        }
+
+        let headers = RatelimitHeaders::from_headers(response.headers())?;
+        ratelimiter.update(method, bucket, &headers).await;
+
+        // Resend the request if it was ratelimited.
+        if response.status() != StatusCode::TOO_MANY_REQUESTS || headers.retry_after.is_none() {
+            return Ok(response);
         }
     }
+}
+
+/// The default ratelimiter, which keeps the ratelimits of a single process in
+/// memory.
+///
+/// Discord identifies ratelimit buckets by an opaque hash, returned in the
+/// `x-ratelimit-bucket` header, that may be shared between routes. The hash of
+/// each route is learned from responses, and routes with the same hash and
+/// major parameter share their ratelimit. Before the hash of a route is known,
+/// the ratelimit is tracked by its [`Method`] and [`Bucket`].
+///
+/// Requests are also limited to 50 per second before they are sent, so that
+/// the global ratelimit is not exceeded. Bots with a raised global ratelimit
+/// can use [`with_global_limit`].
+///
+/// The ratelimits of buckets that have reset and not been used for a minute are
+/// removed periodically, so that memory usage does not grow with the number of
+/// channels and guilds requests have been made in.
+///
+/// [`Method`]: enum.Method.html
+/// [`Bucket`]: enum.Bucket.html
+/// [`with_global_limit`]: #method.with_global_limit
+pub struct DefaultRatelimiter {
+    clock: Arc<dyn Clock>,
+    global: Arc<Mutex<GlobalRatelimit>>,
+    /// The ratelimit bucket hashes of routes, learned from responses.
+    hashes: Arc<RwLock<HashMap<RouteKey, String>>>,
+    routes: Arc<RwLock<HashMap<BucketKey, Arc<Mutex<RateLimit>>>>>,
+    /// When expired buckets were last removed.
+    last_sweep: StdMutex<Instant>,
+}
+
+/// A route's method and bucket variant, which identify its ratelimit bucket
+/// hash regardless of the major parameter.
+type RouteKey = (Method, Discriminant<Bucket>);
 
-    /// Performs a ratelimited request.
-    pub async fn perform(&self, request: &Request<'_>) -> Result<HttpResponse> {
-        let bucket = request.route.bucket();
+/// The key of the ratelimit of a route.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum BucketKey {
+    /// The ratelimit bucket hash of a route and its major parameter.
+    Hash(String, Option<Snowflake>),
+    /// A route whose ratelimit bucket hash is not known.
+    Route(Method, Bucket),
+}
+
+impl DefaultRatelimiter {
+    /// Creates a new ratelimiter.
+    pub fn new() -> DefaultRatelimiter {
+        DefaultRatelimiter::with_global_limit(GLOBAL_RATELIMIT)
+    }
 
-        loop {
-            // Block if the global ratelimit has been reached by another thread.
-            // Drop instantly to prevent blocking other threads.
-            drop(self.global.lock().await);
+    /// Creates a new ratelimiter that sends at most `limit` requests per
+    /// second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is `0`.
+    pub fn with_global_limit(limit: u32) -> DefaultRatelimiter {
+        DefaultRatelimiter::with_clock(limit, Arc::new(SystemClock))
+    }
 
-            let req = request.build(self.token.clone())?;
+    fn with_clock(limit: u32, clock: Arc<dyn Clock>) -> DefaultRatelimiter {
+        assert!(limit > 0, "global ratelimit must be greater than zero");
 
-            // No rate limits apply.
-            if bucket == Bucket::None {}
+        let now = clock.now();
+        DefaultRatelimiter {
+            clock,
+            global: Arc::new(Mutex::new(GlobalRatelimit::new(limit, now))),
+            hashes: Default::default(),
+            routes: Default::default(),
+            last_sweep: StdMutex::new(now),
+        }
+    }
 
-            // Get the ratelimits for the bucket.
-            let bucket_mtx = self.routes.write().await.entry(bucket).or_default().clone();
+    /// Returns the number of buckets whose ratelimits are tracked.
+    ///
+    /// To call this on the ratelimiter of an [`Http`] client, create the
+    /// ratelimiter and keep a clone of it when setting it with
+    /// [`HttpBuilder::ratelimiter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use strife::http::{DefaultRatelimiter, Http};
+    ///
+    /// # async fn run() -> strife::Result<()> {
+    /// let ratelimiter = Arc::new(DefaultRatelimiter::new());
+    /// let http = Http::builder()
+    ///     .token("my token")
+    ///     .ratelimiter(ratelimiter.clone())
+    ///     .build()?;
+    ///
+    /// println!("{} ratelimit buckets", ratelimiter.bucket_count().await);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Http`]: struct.Http.html
+    /// [`HttpBuilder::ratelimiter`]: struct.HttpBuilder.html#method.ratelimiter
+    pub async fn bucket_count(&self) -> usize {
+        self.routes.read().await.len()
+    }
 
-            // Apply pre-request hooks.
-            bucket_mtx.lock().await.pre_hook(&bucket).await;
+    /// Removes the ratelimits of buckets that have reset and not been used for
+    /// a minute, returning the number of buckets removed.
+    ///
+    /// This is done periodically while requests are being sent, so does not
+    /// usually need to be called.
+    pub async fn remove_expired(&self) -> usize {
+        let now = self.clock.now();
+        let mut routes = self.routes.write().await;
+        self.sweep(&mut routes, now)
+    }
 
-            let response = self
-                .client
-                .request(req)
-                .await
-                .map_err(HttpError::HyperError)?;
+    /// Removes the ratelimits of expired buckets.
+    fn sweep(&self, routes: &mut HashMap<BucketKey, Arc<Mutex<RateLimit>>>, now: Instant) -> usize {
+        *self.last_sweep.lock().unwrap() = now;
 
-            // No ratelimits apply to this request.
-            if bucket == Bucket::None {
-                return Ok(response);
+        let len = routes.len();
+        routes.retain(|_, ratelimit| {
+            // Ratelimits in use by a request are kept.
+            if Arc::strong_count(ratelimit) > 1 {
+                return true;
             }
+            match ratelimit.try_lock() {
+                Some(ratelimit) => !ratelimit.is_expired(now),
+                None => true,
+            }
+        });
 
-            // Check if global ratelimit was hit.
-            if response.headers().contains_key(RATELIMIT_GLOBAL) {
-                // Lock on global ratelimit mutex to block other threads.
-                let _global = self.global.lock().await;
+        let removed = len - routes.len();
+        if removed > 0 {
+            log::debug!("Removed {} expired ratelimit buckets", removed);
+        }
+        removed
+    }
 
-                // Parse the retry-after header.
-                match parse_header::<u64>(response.headers(), RETRY_AFTER)? {
-                    Some(retry_after) => {
-                        log::debug!("Ratelimited on bucket {:?} for {}ms", &bucket, retry_after);
+    /// Returns the key of the ratelimit of a route.
+    async fn key(&self, method: Method, bucket: Bucket) -> BucketKey {
+        let route = (method, mem::discriminant(&bucket));
+        match self.hashes.read().await.get(&route) {
+            Some(hash) => BucketKey::Hash(hash.clone(), bucket.major_parameter()),
+            None => BucketKey::Route(method, bucket),
+        }
+    }
 
-                        // Wait for ratelimit delay.
-                        Delay::new(Duration::from_millis(retry_after)).await;
+    /// Returns the ratelimit of a route.
+    ///
+    /// Expired buckets are removed if they have not been removed for a while.
+    async fn ratelimit(&self, method: Method, bucket: Bucket) -> Arc<Mutex<RateLimit>> {
+        let key = self.key(method, bucket).await;
+        let now = self.clock.now();
 
-                        // Request needs to be resent.
-                        continue;
-                    }
-                    None => return Ok(response),
-                }
+        let mut routes = self.routes.write().await;
+        let last_sweep = *self.last_sweep.lock().unwrap();
+        if now.saturating_duration_since(last_sweep) >= SWEEP_INTERVAL {
+            self.sweep(&mut routes, now);
+        }
+        routes.entry(key).or_default().clone()
+    }
+
+    /// Updates the ratelimit bucket hash of a route.
+    ///
+    /// The ratelimit of the route is shared with any other routes with the same
+    /// hash and major parameter.
+    async fn learn_hash(&self, method: Method, bucket: Bucket, hash: &str) {
+        let old_key = self.key(method, bucket).await;
+        let new_key = BucketKey::Hash(hash.to_owned(), bucket.major_parameter());
+        if old_key == new_key {
+            return;
+        }
+
+        log::debug!(
+            "{:?} requests in bucket {:?} have ratelimit bucket hash {}",
+            method,
+            bucket,
+            hash
+        );
+
+        self.hashes
+            .write()
+            .await
+            .insert((method, mem::discriminant(&bucket)), hash.to_owned());
+
+        // The ratelimit tracked before the hash was known is moved to the
+        // hash. If the hash has changed, the ratelimit of the old hash is left
+        // to the routes still using it, and the route starts with a new one
+        // that is updated from the response.
+        if let BucketKey::Route(..) = old_key {
+            let mut routes = self.routes.write().await;
+            if let Some(ratelimit) = routes.remove(&old_key) {
+                routes.entry(new_key).or_insert(ratelimit);
             }
+        }
+    }
+}
 
-            // Check if bucket ratelimit was hit.
-            let resend = {
-                let mut lock = bucket_mtx.lock().await;
-                lock.post_hook(&response, &bucket).await?
-            };
+#[async_trait]
+impl Ratelimiter for DefaultRatelimiter {
+    async fn acquire(&self, method: Method, bucket: Bucket) {
+        // Block if the global ratelimit has been reached, either by another
+        // thread or by sending too many requests. The lock is released as soon
+        // as the request is allowed, to prevent blocking other threads.
+        self.global.lock().await.acquire(&*self.clock).await;
+
+        // No rate limits apply.
+        if bucket == Bucket::None {
+            return;
+        }
+
+        // Apply pre-request hooks.
+        let ratelimit = self.ratelimit(method, bucket).await;
+        let mut lock = ratelimit.lock().await;
+        lock.pre_hook(&bucket, &*self.clock).await;
+        lock.last_used = Some(self.clock.now());
+    }
 
-            // Request was sent, return the response.
-            if !resend {
-                return Ok(response);
+    async fn update(&self, method: Method, bucket: Bucket, headers: &RatelimitHeaders) {
+        // Check if global ratelimit was hit.
+        if headers.global {
+            // Lock on global ratelimit mutex to block other threads.
+            let mut global = self.global.lock().await;
+
+            if let Some(retry_after) = headers.retry_after {
+                log::debug!("Ratelimited on bucket {:?} for {}ms", &bucket, retry_after);
+
+                // Wait for ratelimit delay.
+                self.clock.sleep(Duration::from_millis(retry_after)).await;
             }
 
-            // Request needs to be resent.
+            // Start sending requests again at the limited rate.
+            global.exhaust(self.clock.now());
+            return;
+        }
+
+        // Share the ratelimit with other routes in the same bucket.
+        if let Some(hash) = &headers.bucket {
+            self.learn_hash(method, bucket, hash).await;
+        }
+
+        // Check if bucket ratelimit was hit.
+        let ratelimit = self.ratelimit(method, bucket).await;
+        let mut lock = ratelimit.lock().await;
+        lock.post_hook(headers, &bucket, &*self.clock).await;
+
+        let now = self.clock.now();
+        lock.last_used = Some(now);
+        if let Some(reset_after) = headers.reset_after {
+            lock.reset_at = Some(now + Duration::from_secs_f64(reset_after.max(0.0)));
+        }
+    }
+}
+
+impl Default for DefaultRatelimiter {
+    fn default() -> Self {
+        DefaultRatelimiter::new()
+    }
+}
+
+/// A token bucket limiting the rate at which requests are sent, to avoid
+/// exceeding the global ratelimit.
+struct GlobalRatelimit {
+    /// The number of requests allowed per second.
+    limit: u32,
+    /// The number of requests that can be sent immediately.
+    tokens: f64,
+    /// When the tokens were last refilled.
+    last: Instant,
+}
+
+impl GlobalRatelimit {
+    fn new(limit: u32, now: Instant) -> GlobalRatelimit {
+        GlobalRatelimit {
+            limit,
+            tokens: f64::from(limit),
+            last: now,
         }
     }
+
+    /// Refills the tokens for the time elapsed since they were last refilled.
+    fn refill(&mut self, now: Instant) {
+        let limit = f64::from(self.limit);
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+
+        self.tokens = f64::min(self.tokens + elapsed * limit, limit);
+        self.last = self.last.max(now);
+    }
+
+    /// Waits until a request can be sent.
+    async fn acquire(&mut self, clock: &dyn Clock) {
+        self.refill(clock.now());
+
+        if self.tokens < 1.0 {
+            let delay = Duration::from_secs_f64((1.0 - self.tokens) / f64::from(self.limit));
+            log::debug!("Pre-emptive global ratelimit for {:?}", delay);
+
+            clock.sleep(delay).await;
+            self.refill(clock.now());
+        }
+
+        self.tokens -= 1.0;
+    }
+
+    /// Removes all tokens, after the global ratelimit was exceeded.
+    fn exhaust(&mut self, now: Instant) {
+        self.tokens = 0.0;
+        self.last = now;
+    }
+}
+
+/// A source of time, which can be replaced in tests.
+#[async_trait]
+trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+
+    /// Waits for a duration.
+    async fn sleep(&self, duration: Duration);
+}
+
+/// The system clock.
+struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        Delay::new(duration).await;
+    }
 }
 
 struct RateLimit {
@@ -119,6 +518,10 @@ struct RateLimit {
     reset: i64,
     #[cfg(not(feature = "systime_ratelimits"))]
     reset_after: i64,
+    /// When the bucket was last used.
+    last_used: Option<Instant>,
+    /// When the bucket resets.
+    reset_at: Option<Instant>,
 }
 
 impl RateLimit {
@@ -133,7 +536,21 @@ impl RateLimit {
         self.reset_after
     }
 
-    async fn pre_hook(&mut self, bucket: &Bucket) {
+    /// Returns whether the bucket has reset and not been used for a while, so
+    /// can be removed.
+    fn is_expired(&self, now: Instant) -> bool {
+        let idle = match self.last_used {
+            Some(last_used) => now.saturating_duration_since(last_used) >= BUCKET_IDLE_TIMEOUT,
+            None => true,
+        };
+        let reset = match self.reset_at {
+            Some(reset_at) => reset_at <= now,
+            None => true,
+        };
+        idle && reset
+    }
+
+    async fn pre_hook(&mut self, bucket: &Bucket, clock: &dyn Clock) {
         if self.limit == 0 {
             return;
         }
@@ -154,49 +571,38 @@ impl RateLimit {
                 delay
             );
 
-            Delay::new(Duration::from_millis(delay)).await;
+            clock.sleep(Duration::from_millis(delay)).await;
         }
 
         self.remaining -= 1;
     }
 
-    async fn post_hook(&mut self, response: &HttpResponse, bucket: &Bucket) -> Result<bool> {
-        if let Some(limit) = parse_header(&response.headers(), RATELIMIT_LIMIT)? {
-            self.limit = limit;
+    async fn post_hook(&mut self, headers: &RatelimitHeaders, bucket: &Bucket, clock: &dyn Clock) {
+        if let Some(limit) = headers.limit {
+            self.limit = limit as i64;
         }
 
-        if let Some(remaining) = parse_header(&response.headers(), RATELIMIT_REMAINING)? {
-            self.remaining = remaining;
+        if let Some(remaining) = headers.remaining {
+            self.remaining = remaining as i64;
         }
 
         #[cfg(feature = "systime_ratelimits")]
         {
-            if let Some(reset) = parse_header::<f64>(&response.headers(), RATELIMIT_RESET)? {
+            if let Some(reset) = headers.reset {
                 self.reset = f64::ceil(reset * 1000f64) as i64;
             }
         }
 
         #[cfg(not(feature = "systime_ratelimits"))]
         {
-            if let Some(reset_after) =
-                parse_header::<f64>(&response.headers(), RATELIMIT_RESET_AFTER)?
-            {
+            if let Some(reset_after) = headers.reset_after {
                 self.reset_after = f64::ceil(reset_after * 1000f64) as i64;
             }
         }
 
-        if response.status() != StatusCode::TOO_MANY_REQUESTS {
-            return Ok(false);
-        }
-
-        match parse_header::<u64>(&response.headers(), RETRY_AFTER)? {
-            Some(retry_after) => {
-                log::debug!("Ratelimited on route {:?} for {}ms", bucket, retry_after);
-                Delay::new(Duration::from_millis(retry_after)).await;
-
-                Ok(true)
-            }
-            None => Ok(false),
+        if let Some(retry_after) = headers.retry_after {
+            log::debug!("Ratelimited on route {:?} for {}ms", bucket, retry_after);
+            clock.sleep(Duration::from_millis(retry_after)).await;
         }
     }
 }
@@ -210,6 +616,8 @@ impl Default for RateLimit {
             reset: i64::MAX,
             #[cfg(not(feature = "systime_ratelimits"))]
             reset_after: i64::MAX,
+            last_used: None,
+            reset_at: None,
         }
     }
 }
@@ -233,12 +641,418 @@ fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<
 
 #[cfg(test)]
 mod tests {
+    use std::collections::VecDeque;
     use std::iter::FromIterator;
 
     use hyper::header::{HeaderName, HeaderValue};
+    use hyper::Body;
+
+    use crate::model::id::ChannelId;
 
     use super::*;
 
+    /// A client that responds to requests with queued responses.
+    #[derive(Default)]
+    struct MockClient {
+        responses: StdMutex<VecDeque<HttpResponse>>,
+        requests: StdMutex<Vec<String>>,
+    }
+
+    impl MockClient {
+        fn respond(&self, status: StatusCode, headers: &[(&'static str, &'static str)]) {
+            let mut response = HttpResponse::new(Body::empty());
+            *response.status_mut() = status;
+            for (name, value) in headers {
+                response.headers_mut().insert(
+                    HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                );
+            }
+            self.responses.lock().unwrap().push_back(response);
+        }
+    }
+
+    impl HyperClient for MockClient {
+        fn request(&self, req: HttpRequest) -> ResponseFuture {
+            self.requests.lock().unwrap().push(req.uri().to_string());
+            let response = self.responses.lock().unwrap().pop_front().unwrap();
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Acquire(Method, Bucket),
+        Update(Method, Bucket, RatelimitHeaders),
+    }
+
+    /// An in-memory ratelimiter that records the calls made to it.
+    #[derive(Default)]
+    struct MockRatelimiter {
+        calls: StdMutex<Vec<Call>>,
+    }
+
+    #[async_trait]
+    impl Ratelimiter for MockRatelimiter {
+        async fn acquire(&self, method: Method, bucket: Bucket) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(Call::Acquire(method, bucket));
+        }
+
+        async fn update(&self, method: Method, bucket: Bucket, headers: &RatelimitHeaders) {
+            let headers = headers.clone();
+            self.calls
+                .lock()
+                .unwrap()
+                .push(Call::Update(method, bucket, headers));
+        }
+    }
+
+    /// A clock that advances only when slept on.
+    struct FakeClock {
+        start: Instant,
+        elapsed: StdMutex<Duration>,
+    }
+
+    impl FakeClock {
+        fn new() -> Arc<FakeClock> {
+            Arc::new(FakeClock {
+                start: Instant::now(),
+                elapsed: StdMutex::new(Duration::from_secs(0)),
+            })
+        }
+
+        fn elapsed(&self) -> Duration {
+            *self.elapsed.lock().unwrap()
+        }
+
+        fn advance(&self, duration: Duration) {
+            *self.elapsed.lock().unwrap() += duration;
+        }
+    }
+
+    #[async_trait]
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            self.start + self.elapsed()
+        }
+
+        async fn sleep(&self, duration: Duration) {
+            self.advance(duration);
+        }
+    }
+
+    fn bucket_headers(hash: &str) -> RatelimitHeaders {
+        RatelimitHeaders {
+            bucket: Some(hash.to_owned()),
+            ..RatelimitHeaders::default()
+        }
+    }
+
+    #[async_std::test]
+    async fn test_perform() {
+        let client = MockClient::default();
+        client.respond(
+            StatusCode::TOO_MANY_REQUESTS,
+            &[(RATELIMIT_REMAINING, "0"), (RETRY_AFTER, "0")],
+        );
+        client.respond(StatusCode::OK, &[(RATELIMIT_REMAINING, "4")]);
+
+        let ratelimiter = MockRatelimiter::default();
+        let channel_id = ChannelId::from(1234);
+        let request = Request::new(Route::GetChannel { channel_id });
+        let bucket = Bucket::ChannelsId(channel_id);
+
+        let invalid_requests = InvalidRequests::default();
+        let config = RequestConfig::default();
+
+        let response = perform(&client, &ratelimiter, &invalid_requests, &config, &request)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // The ratelimited request is resent.
+        assert_eq!(client.requests.lock().unwrap().len(), 2);
+        assert_eq!(invalid_requests.count(Instant::now()), 1);
+        assert_eq!(
+            *ratelimiter.calls.lock().unwrap(),
+            [
+                Call::Acquire(Method::Get, bucket),
+                Call::Update(
+                    Method::Get,
+                    bucket,
+                    RatelimitHeaders {
+                        remaining: Some(0),
+                        retry_after: Some(0),
+                        ..RatelimitHeaders::default()
+                    }
+                ),
+                Call::Acquire(Method::Get, bucket),
+                Call::Update(
+                    Method::Get,
+                    bucket,
+                    RatelimitHeaders {
+                        remaining: Some(4),
+                        ..RatelimitHeaders::default()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[async_std::test]
+    async fn test_global_ratelimit() {
+        let clock = FakeClock::new();
+        let mut global = GlobalRatelimit::new(GLOBAL_RATELIMIT, clock.now());
+
+        // A burst of requests up to the limit is allowed.
+        for _ in 0..GLOBAL_RATELIMIT {
+            global.acquire(&*clock).await;
+        }
+        assert_eq!(clock.elapsed(), Duration::from_secs(0));
+
+        // Further requests are spaced evenly.
+        global.acquire(&*clock).await;
+        assert_eq!(clock.elapsed(), Duration::from_millis(20));
+        global.acquire(&*clock).await;
+        assert_eq!(clock.elapsed(), Duration::from_millis(40));
+
+        // Tokens do not accumulate beyond the limit.
+        clock.advance(Duration::from_secs(10));
+        let start = clock.elapsed();
+        for _ in 0..=GLOBAL_RATELIMIT {
+            global.acquire(&*clock).await;
+        }
+        assert_eq!(clock.elapsed() - start, Duration::from_millis(20));
+    }
+
+    #[async_std::test]
+    async fn test_raised_global_ratelimit() {
+        let clock = FakeClock::new();
+        let ratelimiter = DefaultRatelimiter::with_clock(500, clock.clone());
+
+        for _ in 0..=500 {
+            ratelimiter.acquire(Method::Get, Bucket::Guilds).await;
+        }
+        assert_eq!(clock.elapsed(), Duration::from_millis(2));
+    }
+
+    #[async_std::test]
+    async fn test_global_ratelimit_exceeded() {
+        let clock = FakeClock::new();
+        let ratelimiter = DefaultRatelimiter::with_clock(GLOBAL_RATELIMIT, clock.clone());
+        let headers = RatelimitHeaders {
+            global: true,
+            retry_after: Some(1000),
+            ..RatelimitHeaders::default()
+        };
+
+        ratelimiter.acquire(Method::Get, Bucket::Guilds).await;
+        ratelimiter
+            .update(Method::Get, Bucket::Guilds, &headers)
+            .await;
+        assert_eq!(clock.elapsed(), Duration::from_secs(1));
+
+        // Requests resume at the limited rate, rather than in a burst.
+        ratelimiter.acquire(Method::Get, Bucket::Guilds).await;
+        assert_eq!(clock.elapsed(), Duration::from_millis(1020));
+    }
+
+    #[async_std::test]
+    async fn test_bucket_ratelimit() {
+        let clock = FakeClock::new();
+        let ratelimiter = DefaultRatelimiter::with_clock(GLOBAL_RATELIMIT, clock.clone());
+        let bucket = Bucket::ChannelsIdMessages(ChannelId::from(1));
+
+        // A ratelimited response waits before the request is resent.
+        let headers = RatelimitHeaders {
+            limit: Some(5),
+            remaining: Some(0),
+            reset_after: Some(2.0),
+            retry_after: Some(2000),
+            ..RatelimitHeaders::default()
+        };
+        ratelimiter.acquire(Method::Post, bucket).await;
+        ratelimiter.update(Method::Post, bucket, &headers).await;
+        assert_eq!(clock.elapsed(), Duration::from_secs(2));
+
+        // An exhausted bucket waits before the request is sent.
+        ratelimiter.acquire(Method::Post, bucket).await;
+        assert_eq!(clock.elapsed(), Duration::from_secs(4));
+    }
+
+    fn reset_headers(reset_after: f64) -> RatelimitHeaders {
+        RatelimitHeaders {
+            limit: Some(5),
+            remaining: Some(4),
+            reset_after: Some(reset_after),
+            ..RatelimitHeaders::default()
+        }
+    }
+
+    #[async_std::test]
+    async fn test_remove_expired() {
+        let clock = FakeClock::new();
+        let ratelimiter = DefaultRatelimiter::with_clock(GLOBAL_RATELIMIT, clock.clone());
+
+        for id in 0..3 {
+            let bucket = Bucket::ChannelsIdMessages(ChannelId::from(id));
+            ratelimiter.acquire(Method::Get, bucket).await;
+            ratelimiter
+                .update(Method::Get, bucket, &reset_headers(1.0))
+                .await;
+        }
+        assert_eq!(ratelimiter.bucket_count().await, 3);
+
+        // Buckets that have reset are kept until they are idle.
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(ratelimiter.remove_expired().await, 0);
+
+        clock.advance(BUCKET_IDLE_TIMEOUT);
+        assert_eq!(ratelimiter.remove_expired().await, 3);
+        assert_eq!(ratelimiter.bucket_count().await, 0);
+    }
+
+    #[async_std::test]
+    async fn test_remove_expired_lazily() {
+        let clock = FakeClock::new();
+        let ratelimiter = DefaultRatelimiter::with_clock(GLOBAL_RATELIMIT, clock.clone());
+        let idle = Bucket::ChannelsIdMessages(ChannelId::from(1));
+        let not_reset = Bucket::ChannelsIdMessages(ChannelId::from(2));
+        let in_use = Bucket::ChannelsIdMessages(ChannelId::from(3));
+
+        ratelimiter.acquire(Method::Get, idle).await;
+        ratelimiter
+            .update(Method::Get, idle, &reset_headers(1.0))
+            .await;
+        ratelimiter.acquire(Method::Get, not_reset).await;
+        ratelimiter
+            .update(Method::Get, not_reset, &reset_headers(600.0))
+            .await;
+        let _ratelimit = ratelimiter.ratelimit(Method::Get, in_use).await;
+
+        // Expired buckets are removed when a bucket is next used.
+        clock.advance(SWEEP_INTERVAL);
+        ratelimiter.acquire(Method::Get, Bucket::Guilds).await;
+
+        let routes = ratelimiter.routes.read().await;
+        assert_eq!(routes.len(), 3);
+        assert!(!routes.contains_key(&BucketKey::Route(Method::Get, idle)));
+        assert!(routes.contains_key(&BucketKey::Route(Method::Get, not_reset)));
+        assert!(routes.contains_key(&BucketKey::Route(Method::Get, in_use)));
+        assert!(routes.contains_key(&BucketKey::Route(Method::Get, Bucket::Guilds)));
+    }
+
+    #[async_std::test]
+    async fn test_learn_hash() {
+        let ratelimiter = DefaultRatelimiter::new();
+        let bucket = Bucket::ChannelsIdMessages(ChannelId::from(1));
+        let other_channel = Bucket::ChannelsIdMessages(ChannelId::from(2));
+
+        let key = ratelimiter.key(Method::Get, bucket).await;
+        assert_eq!(key, BucketKey::Route(Method::Get, bucket));
+
+        let ratelimit = ratelimiter.ratelimit(Method::Get, bucket).await;
+        ratelimiter
+            .update(Method::Get, bucket, &bucket_headers("abcd"))
+            .await;
+
+        let hash_key = BucketKey::Hash("abcd".to_owned(), Some(Snowflake::from(1)));
+        assert_eq!(ratelimiter.key(Method::Get, bucket).await, hash_key);
+        assert_eq!(
+            ratelimiter.key(Method::Get, other_channel).await,
+            BucketKey::Hash("abcd".to_owned(), Some(Snowflake::from(2)))
+        );
+
+        // The state of the route is moved to the hash.
+        let routes = ratelimiter.routes.read().await;
+        assert!(!routes.contains_key(&key));
+        assert!(Arc::ptr_eq(&routes[&hash_key], &ratelimit));
+    }
+
+    #[async_std::test]
+    async fn test_changed_hash() {
+        let clock = FakeClock::new();
+        let ratelimiter = DefaultRatelimiter::with_clock(GLOBAL_RATELIMIT, clock.clone());
+        let bucket = Bucket::ChannelsIdMessages(ChannelId::from(1));
+        let old_key = BucketKey::Hash("abcd".to_owned(), Some(Snowflake::from(1)));
+        let new_key = BucketKey::Hash("efgh".to_owned(), Some(Snowflake::from(1)));
+
+        for hash in &["abcd", "efgh"] {
+            let headers = RatelimitHeaders {
+                bucket: Some((*hash).to_owned()),
+                ..reset_headers(1.0)
+            };
+            ratelimiter.acquire(Method::Get, bucket).await;
+            ratelimiter.update(Method::Get, bucket, &headers).await;
+        }
+
+        // The route has a new ratelimit, rather than sharing the old one.
+        {
+            let routes = ratelimiter.routes.read().await;
+            assert_eq!(routes.len(), 2);
+            assert!(!Arc::ptr_eq(&routes[&old_key], &routes[&new_key]));
+        }
+
+        // Both ratelimits expire.
+        clock.advance(SWEEP_INTERVAL + BUCKET_IDLE_TIMEOUT);
+        assert_eq!(ratelimiter.remove_expired().await, 2);
+        assert_eq!(ratelimiter.bucket_count().await, 0);
+    }
+
+    #[async_std::test]
+    async fn test_shared_hash() {
+        let ratelimiter = DefaultRatelimiter::new();
+        let messages = Bucket::ChannelsIdMessages(ChannelId::from(1));
+        let message = Bucket::ChannelsIdMessagesId(ChannelId::from(1));
+        let headers = bucket_headers("abcd");
+
+        let ratelimit = ratelimiter.ratelimit(Method::Get, messages).await;
+        ratelimiter.update(Method::Get, messages, &headers).await;
+
+        ratelimiter.acquire(Method::Get, message).await;
+        ratelimiter.update(Method::Get, message, &headers).await;
+
+        // Both routes share the ratelimit learned first.
+        assert_eq!(
+            ratelimiter.key(Method::Get, messages).await,
+            ratelimiter.key(Method::Get, message).await
+        );
+        assert!(Arc::ptr_eq(
+            &ratelimiter.ratelimit(Method::Get, message).await,
+            &ratelimit
+        ));
+    }
+
+    #[async_std::test]
+    async fn test_hash_per_method() {
+        let ratelimiter = DefaultRatelimiter::new();
+        let bucket = Bucket::ChannelsIdMessages(ChannelId::from(1));
+        let get_key = BucketKey::Hash("abcd".to_owned(), Some(Snowflake::from(1)));
+        let post_key = BucketKey::Hash("efgh".to_owned(), Some(Snowflake::from(1)));
+
+        for _ in 0..2 {
+            ratelimiter.acquire(Method::Get, bucket).await;
+            ratelimiter
+                .update(Method::Get, bucket, &bucket_headers("abcd"))
+                .await;
+            ratelimiter.acquire(Method::Post, bucket).await;
+            ratelimiter
+                .update(Method::Post, bucket, &bucket_headers("efgh"))
+                .await;
+        }
+
+        // Each method keeps the hash learned for it.
+        assert_eq!(ratelimiter.key(Method::Get, bucket).await, get_key);
+        assert_eq!(ratelimiter.key(Method::Post, bucket).await, post_key);
+
+        let routes = ratelimiter.routes.read().await;
+        assert_eq!(routes.len(), 2);
+        assert!(!Arc::ptr_eq(&routes[&get_key], &routes[&post_key]));
+    }
+
     #[test]
     fn test_parse_header() {
         let headers = HeaderMap::from_iter(vec![
@@ -285,4 +1099,41 @@ mod tests {
             6.457
         );
     }
+
+    #[test]
+    fn test_ratelimit_headers() {
+        let headers = HeaderMap::from_iter(vec![
+            (
+                HeaderName::from_static(RATELIMIT_BUCKET),
+                HeaderValue::from_static("abcd1234"),
+            ),
+            (
+                HeaderName::from_static(RATELIMIT_GLOBAL),
+                HeaderValue::from_static("true"),
+            ),
+            (
+                HeaderName::from_static(RATELIMIT_REMAINING),
+                HeaderValue::from_static("0"),
+            ),
+            (
+                HeaderName::from_static(RETRY_AFTER),
+                HeaderValue::from_static("6457"),
+            ),
+        ]);
+
+        assert_eq!(
+            RatelimitHeaders::from_headers(&headers).unwrap(),
+            RatelimitHeaders {
+                bucket: Some("abcd1234".to_owned()),
+                global: true,
+                remaining: Some(0),
+                retry_after: Some(6457),
+                ..RatelimitHeaders::default()
+            }
+        );
+        assert_eq!(
+            RatelimitHeaders::from_headers(&HeaderMap::new()).unwrap(),
+            RatelimitHeaders::default()
+        );
+    }
 }
@@ -1,16 +1,23 @@
-use bytes::Bytes;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::{BufMut, Bytes, BytesMut};
 use hyper::body::Body;
 use hyper::header::{
     HeaderMap, HeaderValue, IntoHeaderName, AUTHORIZATION, CONTENT_TYPE, USER_AGENT,
 };
 use serde::Serialize;
 
-use crate::constants;
+use crate::builder::ValidationError;
+use crate::constants::{self, AUDIT_LOG_REASON_MAX_LENGTH};
 use crate::internal::prelude::*;
 
 use super::prelude::*;
+use super::routing::percent_encode;
 
 const RATELIMIT_PRECISION: &str = "x-ratelimit-precision";
+const AUDIT_LOG_REASON: &str = "x-audit-log-reason";
 
 static APPLICATION_JSON: Bytes = Bytes::from_static(b"application/json");
 static MILLISECOND: Bytes = Bytes::from_static(b"millisecond");
@@ -93,6 +100,33 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Sets the reason shown in the audit log entry for the request.
+    ///
+    /// The reason is percent-encoded and sent in the `x-audit-log-reason`
+    /// header.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ValidationError`] if the reason is longer than
+    /// [`AUDIT_LOG_REASON_MAX_LENGTH`] code points.
+    ///
+    /// [`ValidationError`]: ../../builder/enum.ValidationError.html
+    /// [`AUDIT_LOG_REASON_MAX_LENGTH`]: ../../constants/constant.AUDIT_LOG_REASON_MAX_LENGTH.html
+    pub fn reason(&mut self, reason: &str) -> Result<&mut Self> {
+        let length = reason.chars().count();
+        if length > AUDIT_LOG_REASON_MAX_LENGTH {
+            return Err(ValidationError::AuditLogReasonTooLong {
+                length,
+                max: AUDIT_LOG_REASON_MAX_LENGTH,
+            }
+            .into());
+        }
+
+        let mut encoded = String::with_capacity(reason.len());
+        let _ = percent_encode(&mut encoded, reason);
+        self.header(AUDIT_LOG_REASON, encoded)
+    }
+
     /// Sets the body of the request.
     pub fn body<B>(&mut self, body: B) -> &mut Self
     where
@@ -112,12 +146,29 @@ impl<'a> Request<'a> {
         Ok(self)
     }
 
-    pub(crate) fn build<T>(&self, token: T) -> Result<HttpRequest>
-    where
-        T: Into<Bytes>,
-    {
+    /// Sets a multipart form as the body of the request, replacing the
+    /// `content-type` header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidHeader`] error if the content type of a file is not
+    /// a valid header value.
+    ///
+    /// [`InvalidHeader`]: enum.HttpError.html#variant.InvalidHeader
+    pub fn multipart(&mut self, form: &Multipart) -> Result<&mut Self> {
+        let (boundary, body) = form.build()?;
+
+        self.header(
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={}", boundary),
+        )?;
+        self.body = Some(body);
+        Ok(self)
+    }
+
+    pub(crate) fn build(&self, config: &RequestConfig) -> Result<HttpRequest> {
         // Create header map.
-        let headers = self.build_headers(token.into())?;
+        let headers = self.build_headers(config)?;
 
         // Build request.
         let route = &self.route;
@@ -126,24 +177,26 @@ impl<'a> Request<'a> {
         let mut req = HttpRequest::new(Body::from(body));
 
         *req.method_mut() = route.method().into();
-        *req.uri_mut() = route.url().parse().map_err(HttpError::InvalidUri)?;
+        *req.uri_mut() = config.url(route).parse().map_err(HttpError::InvalidUri)?;
         *req.headers_mut() = headers;
 
         Ok(req)
     }
 
-    fn build_headers(&self, token: Bytes) -> Result<HeaderMap> {
+    fn build_headers(&self, config: &RequestConfig) -> Result<HeaderMap> {
         let mut headers = self.headers.clone().unwrap_or_default();
 
         // Reserve space for headers.
         headers.reserve(4);
 
-        let auth = {
+        // Requests authenticated by a token in the URL, such as webhooks, do
+        // not require an authorization header.
+        if let Some(token) = &config.token {
             let mut auth = HeaderValue::from_maybe_shared(token.clone())
-                .map_err(|_| HttpError::InvalidHeader(token))?;
+                .map_err(|_| HttpError::InvalidHeader(token.clone()))?;
             auth.set_sensitive(true);
-            auth
-        };
+            headers.insert(AUTHORIZATION, auth);
+        }
 
         // Use unsafe `from_maybe_shared_unchecked` function to remove checks for
         // invalid bytes, since we can validate this before compile time.
@@ -152,8 +205,7 @@ impl<'a> Request<'a> {
         let millisecond = unsafe { HeaderValue::from_maybe_shared_unchecked(MILLISECOND.clone()) };
 
         // Add base headers, cannot be overridden by custom headers.
-        headers.insert(USER_AGENT, HeaderValue::from_static(constants::USER_AGENT));
-        headers.insert(AUTHORIZATION, auth);
+        headers.insert(USER_AGENT, config.user_agent.clone());
         headers.insert(RATELIMIT_PRECISION, millisecond);
 
         // Allow content-type to be overridden by custom headers.
@@ -165,3 +217,407 @@ impl<'a> Request<'a> {
         Ok(headers)
     }
 }
+
+/// Settings applied to every request sent by an [`Http`] client.
+///
+/// [`Http`]: ../struct.Http.html
+#[derive(Clone, Debug)]
+pub(crate) struct RequestConfig {
+    /// The authorization token, or `None` to send requests without one.
+    pub token: Option<Bytes>,
+    /// The base URL of the API, or `None` to use the Discord API.
+    pub base_url: Option<String>,
+    /// The value of the `user-agent` header.
+    pub user_agent: HeaderValue,
+}
+
+impl RequestConfig {
+    /// Returns the URL of a route, relative to the base URL.
+    pub fn url<'a>(&self, route: &Route<'a>) -> Cow<'a, str> {
+        let url = route.url();
+        match &self.base_url {
+            Some(base_url) => {
+                let path = &url[api!().len()..];
+                Cow::from(format!("{}{}", base_url.trim_end_matches('/'), path))
+            }
+            None => url,
+        }
+    }
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        RequestConfig {
+            token: None,
+            base_url: None,
+            user_agent: HeaderValue::from_static(constants::USER_AGENT),
+        }
+    }
+}
+
+/// A file to be uploaded as part of a [`Multipart`] form.
+///
+/// [`Multipart`]: struct.Multipart.html
+#[derive(Clone, Debug)]
+pub struct FileUpload {
+    filename: String,
+    content_type: Option<String>,
+    data: Bytes,
+}
+
+impl FileUpload {
+    /// Creates a file upload with the given filename and contents.
+    pub fn new<S, B>(filename: S, data: B) -> FileUpload
+    where
+        S: Into<String>,
+        B: Into<Bytes>,
+    {
+        FileUpload {
+            filename: filename.into(),
+            content_type: None,
+            data: data.into(),
+        }
+    }
+
+    /// Sets the content type of the file.
+    ///
+    /// Defaults to `application/octet-stream`. The content type must be a
+    /// valid header value, or setting the form as the body of a request will
+    /// fail.
+    pub fn content_type<S: Into<String>>(mut self, content_type: S) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Returns the filename of the file.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Returns the contents of the file.
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+}
+
+/// A `multipart/form-data` body, consisting of a JSON payload and any number
+/// of files.
+///
+/// # Stability
+///
+/// This is not part of the stable API and may change at any time. For a stable
+/// API use the functions on the [`Http`] client.
+///
+/// [`Http`]: ../struct.Http.html
+#[derive(Clone, Debug, Default)]
+pub struct Multipart {
+    payload_json: Option<Bytes>,
+    files: Vec<FileUpload>,
+}
+
+impl Multipart {
+    /// Creates an empty multipart form.
+    pub fn new() -> Multipart {
+        Multipart::default()
+    }
+
+    /// Serializes a value as JSON and sets it as the `payload_json` part of the
+    /// form.
+    pub fn payload_json<T>(&mut self, value: &T) -> Result<&mut Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        let payload = serde_json::to_vec(value)?;
+        self.payload_json = Some(Bytes::from(payload));
+        Ok(self)
+    }
+
+    /// Adds a file to the form.
+    pub fn file(&mut self, file: FileUpload) -> &mut Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Adds a number of files to the form.
+    pub fn files<I>(&mut self, files: I) -> &mut Self
+    where
+        I: IntoIterator<Item = FileUpload>,
+    {
+        self.files.extend(files);
+        self
+    }
+
+    /// Builds the body of the form, returning the boundary and the body.
+    fn build(&self) -> Result<(String, Bytes)> {
+        let boundary = self.boundary();
+
+        let len = self.payload_json.as_ref().map_or(0, Bytes::len)
+            + self.files.iter().map(|f| f.data.len()).sum::<usize>();
+        let mut body = BytesMut::with_capacity(len + 256 * (self.files.len() + 1));
+
+        if let Some(payload) = &self.payload_json {
+            write_part_header(
+                &mut body,
+                &boundary,
+                "payload_json",
+                None,
+                "application/json",
+            );
+            body.put_slice(payload);
+            body.put_slice(b"\r\n");
+        }
+
+        for (i, file) in self.files.iter().enumerate() {
+            let content_type = file
+                .content_type
+                .as_deref()
+                .unwrap_or("application/octet-stream");
+            // The content type is written into the part header as is, so it
+            // must not contain line breaks or other illegal bytes.
+            if HeaderValue::from_str(content_type).is_err() {
+                return Err(HttpError::InvalidHeader(Bytes::copy_from_slice(
+                    content_type.as_bytes(),
+                ))
+                .into());
+            }
+
+            write_part_header(
+                &mut body,
+                &boundary,
+                &format!("file{}", i),
+                Some(&file.filename),
+                content_type,
+            );
+            body.put_slice(&file.data);
+            body.put_slice(b"\r\n");
+        }
+
+        body.put_slice(b"--");
+        body.put_slice(boundary.as_bytes());
+        body.put_slice(b"--\r\n");
+
+        Ok((boundary, body.freeze()))
+    }
+
+    /// Generates a boundary that does not occur in any part of the form.
+    fn boundary(&self) -> String {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+
+        loop {
+            let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let boundary = format!("strife-boundary-{:08x}{:08x}", nanos, count);
+
+            let parts = self
+                .payload_json
+                .iter()
+                .chain(self.files.iter().map(|f| &f.data));
+            if !parts
+                .into_iter()
+                .any(|part| contains(part, boundary.as_bytes()))
+            {
+                return boundary;
+            }
+        }
+    }
+}
+
+fn write_part_header(
+    body: &mut BytesMut,
+    boundary: &str,
+    name: &str,
+    filename: Option<&str>,
+    content_type: &str,
+) {
+    body.put_slice(b"--");
+    body.put_slice(boundary.as_bytes());
+    body.put_slice(b"\r\nContent-Disposition: form-data; name=\"");
+    body.put_slice(name.as_bytes());
+    body.put_slice(b"\"");
+    if let Some(filename) = filename {
+        body.put_slice(b"; filename=\"");
+        body.put_slice(escape_filename(filename).as_bytes());
+        body.put_slice(b"\"");
+    }
+    body.put_slice(b"\r\nContent-Type: ");
+    body.put_slice(content_type.as_bytes());
+    body.put_slice(b"\r\n\r\n");
+}
+
+/// Escapes characters that cannot appear in a quoted filename.
+fn escape_filename(filename: &str) -> String {
+    filename
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+    use serde_json::{json, Value};
+
+    use crate::model::id::ChannelId;
+
+    use super::*;
+
+    async fn parse(req: &Request<'_>) -> Vec<(String, Option<String>, String, Vec<u8>)> {
+        let headers = req.headers.as_ref().unwrap();
+        let content_type = headers[CONTENT_TYPE].to_str().unwrap();
+        let boundary = multer::parse_boundary(content_type).unwrap();
+
+        let body = req.body.clone().unwrap().to_vec();
+        let stream = stream::once(async move { Ok::<_, std::convert::Infallible>(body) });
+        let mut multipart = multer::Multipart::new(stream, boundary);
+
+        let mut parts = Vec::new();
+        while let Some(field) = multipart.next_field().await.unwrap() {
+            let name = field.name().unwrap().to_owned();
+            let filename = field.file_name().map(str::to_owned);
+            let content_type = field.content_type().unwrap().to_string();
+            let data = field.bytes().await.unwrap().to_vec();
+            parts.push((name, filename, content_type, data));
+        }
+        parts
+    }
+
+    #[test]
+    fn test_build_headers() {
+        let req = Request::new(Route::GetGateway);
+        let mut config = RequestConfig {
+            token: Some(Bytes::from_static(b"Bot token")),
+            ..RequestConfig::default()
+        };
+
+        let headers = req.build_headers(&config).unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bot token");
+        assert!(headers[AUTHORIZATION].is_sensitive());
+
+        config.token = None;
+        let headers = req.build_headers(&config).unwrap();
+        assert!(!headers.contains_key(AUTHORIZATION));
+        assert_eq!(headers[USER_AGENT], constants::USER_AGENT);
+
+        config.user_agent = HeaderValue::from_static("MyBot (https://example.com, 1.0)");
+        let headers = req.build_headers(&config).unwrap();
+        assert_eq!(headers[USER_AGENT], "MyBot (https://example.com, 1.0)");
+    }
+
+    #[test]
+    fn test_config_url() {
+        let route = Route::GetChannel {
+            channel_id: ChannelId::from(1234),
+        };
+        let mut config = RequestConfig::default();
+        assert_eq!(config.url(&route), api!("/channels/1234"));
+
+        config.base_url = Some("http://localhost:8080/api/v6/".to_owned());
+        assert_eq!(
+            config.url(&route),
+            "http://localhost:8080/api/v6/channels/1234"
+        );
+    }
+
+    #[test]
+    fn test_reason() {
+        let mut req = Request::new(Route::GetGateway);
+        req.reason("Spamming in #général").unwrap();
+
+        let headers = req.headers.as_ref().unwrap();
+        assert_eq!(
+            headers[AUDIT_LOG_REASON],
+            "Spamming%20in%20%23g%C3%A9n%C3%A9ral"
+        );
+    }
+
+    #[test]
+    fn test_reason_too_long() {
+        let mut req = Request::new(Route::GetGateway);
+        assert!(req.reason(&"é".repeat(AUDIT_LOG_REASON_MAX_LENGTH)).is_ok());
+
+        let reason = "a".repeat(AUDIT_LOG_REASON_MAX_LENGTH + 1);
+        match req.reason(&reason) {
+            Err(Error::ValidationError(err)) => assert_eq!(
+                err,
+                ValidationError::AuditLogReasonTooLong {
+                    length: AUDIT_LOG_REASON_MAX_LENGTH + 1,
+                    max: AUDIT_LOG_REASON_MAX_LENGTH,
+                }
+            ),
+            _ => panic!("expected validation error"),
+        }
+    }
+
+    #[async_std::test]
+    async fn test_multipart() {
+        let mut form = Multipart::new();
+        form.payload_json(&json!({"content": "Hello, world!"}))
+            .unwrap()
+            .file(FileUpload::new("report.txt", "some text").content_type("text/plain"))
+            .file(FileUpload::new(
+                "image.png",
+                vec![0x89, b'P', b'N', b'G', 0, 0xff],
+            ));
+
+        let mut req = Request::new(Route::GetGateway);
+        req.multipart(&form).unwrap();
+
+        let parts = parse(&req).await;
+        assert_eq!(parts.len(), 3);
+
+        let (name, filename, content_type, data) = &parts[0];
+        assert_eq!(name, "payload_json");
+        assert_eq!(filename, &None);
+        assert_eq!(content_type, "application/json");
+        let payload: Value = serde_json::from_slice(data).unwrap();
+        assert_eq!(payload, json!({"content": "Hello, world!"}));
+
+        let (name, filename, content_type, data) = &parts[1];
+        assert_eq!(name, "file0");
+        assert_eq!(filename.as_deref(), Some("report.txt"));
+        assert_eq!(content_type, "text/plain");
+        assert_eq!(data, b"some text");
+
+        let (name, filename, content_type, data) = &parts[2];
+        assert_eq!(name, "file1");
+        assert_eq!(filename.as_deref(), Some("image.png"));
+        assert_eq!(content_type, "application/octet-stream");
+        assert_eq!(data, &[0x89, b'P', b'N', b'G', 0, 0xff]);
+    }
+
+    #[async_std::test]
+    async fn test_multipart_escape_filename() {
+        let mut form = Multipart::new();
+        form.file(FileUpload::new("a \"quoted\"\r\nname.txt", "data"));
+
+        let mut req = Request::new(Route::GetGateway);
+        req.multipart(&form).unwrap();
+
+        let parts = parse(&req).await;
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].1.as_deref(), Some("a %22quoted%22%0D%0Aname.txt"));
+        assert_eq!(parts[0].3, b"data");
+    }
+
+    #[test]
+    fn test_multipart_invalid_content_type() {
+        let mut form = Multipart::new();
+        form.file(FileUpload::new("a.txt", "data").content_type("text/plain\r\nX-Injected: 1"));
+
+        let mut req = Request::new(Route::GetGateway);
+        match req.multipart(&form) {
+            Err(Error::HttpError(HttpError::InvalidHeader(value))) => {
+                assert_eq!(value, "text/plain\r\nX-Injected: 1");
+            }
+            _ => panic!("expected invalid header error"),
+        }
+    }
+}
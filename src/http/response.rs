@@ -0,0 +1,103 @@
+use bytes::Bytes;
+use hyper::header::HeaderMap;
+use hyper::StatusCode;
+use serde::de::DeserializeOwned;
+
+use crate::internal::prelude::*;
+
+use super::prelude::*;
+
+/// A response to a request sent by the [`Http`] client.
+///
+/// # Stability
+///
+/// This is not part of the stable API and may change at any time. For a stable
+/// API use the functions on the [`Http`] client.
+///
+/// [`Http`]: ../struct.Http.html
+#[derive(Clone, Debug)]
+pub struct Response {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl Response {
+    /// Reads the body of an HTTP response into memory.
+    pub(crate) async fn from_http(response: HttpResponse) -> Result<Response> {
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .map_err(HttpError::HyperError)?;
+
+        Ok(Response {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        })
+    }
+
+    /// Returns the status code of the response.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the headers of the response.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the raw body of the response.
+    ///
+    /// The body is empty for responses with the status `204 No Content`.
+    pub fn bytes(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Consumes the response, returning the raw body.
+    pub fn into_bytes(self) -> Bytes {
+        self.body
+    }
+
+    /// Deserializes the body of the response from JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn response(status: StatusCode, body: &'static [u8]) -> HttpResponse {
+        hyper::Response::builder()
+            .status(status)
+            .header("x-ratelimit-limit", "5")
+            .body(hyper::Body::from(body))
+            .unwrap()
+    }
+
+    #[async_std::test]
+    async fn test_no_content() {
+        let response = Response::from_http(response(StatusCode::NO_CONTENT, b""))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.headers()["x-ratelimit-limit"], "5");
+        assert!(response.bytes().is_empty());
+        assert!(response.json::<Value>().is_err());
+    }
+
+    #[async_std::test]
+    async fn test_json() {
+        let response = Response::from_http(response(StatusCode::OK, b"{\"id\":\"1\"}"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.json::<Value>().unwrap(), json!({"id": "1"}));
+    }
+}
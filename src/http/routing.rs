@@ -3,13 +3,14 @@
 //! Not part of the public API.
 
 use std::borrow::Cow;
-use std::fmt::Write;
+use std::fmt::{self, Display, Write};
 
 use hyper::Method as HttpMethod;
 
 use crate::model::channel::permissions::OverwriteId;
-use crate::model::guild::{AuditLogEvent, Emoji};
+use crate::model::guild::{AuditLogEvent, PartialEmoji};
 use crate::model::id::*;
+use crate::model::snowflake::{Snowflake, ToSnowflake};
 
 /// Buckets grouping [rate limited] routes.
 ///
@@ -280,6 +281,62 @@ pub enum Bucket {
     None,
 }
 
+impl Bucket {
+    /// Returns the major parameter of the bucket.
+    ///
+    /// Routes that share a ratelimit bucket hash only share their ratelimit if
+    /// they also have the same major parameter.
+    pub fn major_parameter(&self) -> Option<Snowflake> {
+        use self::Bucket::*;
+
+        match *self {
+            ChannelsId(id)
+            | ChannelsIdInvites(id)
+            | ChannelsIdMessages(id)
+            | ChannelsIdMessagesBulkDelete(id)
+            | ChannelsIdMessagesId(id)
+            | ChannelsIdMessagesIdDelete(id)
+            | ChannelsIdMessagesIdReactions(id)
+            | ChannelsIdMessagesIdReactionsEmoji(id)
+            | ChannelsIdMessagesIdReactionsEmojiUserId(id)
+            | ChannelsIdPermissionsOverwriteId(id)
+            | ChannelsIdPins(id)
+            | ChannelsIdPinsMessageId(id)
+            | ChannelsIdRecipientsUserId(id)
+            | ChannelsIdTyping(id)
+            | ChannelsIdWebhooks(id) => Some(id.snowflake()),
+
+            GuildsId(id)
+            | GuildsIdAuditLogs(id)
+            | GuildsIdBans(id)
+            | GuildsIdBansUserId(id)
+            | GuildsIdChannels(id)
+            | GuildsIdEmbed(id)
+            | GuildsIdEmojis(id)
+            | GuildsIdEmojisId(id)
+            | GuildsIdIntegrations(id)
+            | GuildsIdIntegrationsId(id)
+            | GuildsIdIntegrationsIdSync(id)
+            | GuildsIdInvites(id)
+            | GuildsIdMembers(id)
+            | GuildsIdMembersId(id)
+            | GuildsIdMembersIdRolesId(id)
+            | GuildsIdMembersMeNick(id)
+            | GuildsIdPrune(id)
+            | GuildsIdRegions(id)
+            | GuildsIdRoles(id)
+            | GuildsIdRolesId(id)
+            | GuildsIdVanityUrl(id)
+            | GuildsIdWebhooks(id) => Some(id.snowflake()),
+
+            WebhooksId(id) | WebhooksIdToken(id) => Some(id.snowflake()),
+
+            Gateway | GatewayBot | Guilds | InvitesCode | UsersId | UsersMeChannels
+            | UsersMeGuilds | UsersMeGuildsId(_) | VoiceRegions | None => Option::None,
+        }
+    }
+}
+
 // TODO: Add support for status api (https://status.discordapp.com/api/).
 /// An API endpoint.
 ///
@@ -307,7 +364,6 @@ pub enum Route<'a> {
         guild_id: GuildId,
         user_id: UserId,
         delete_message_days: Option<u8>,
-        reason: Option<&'a str>,
     },
     BroadcastTyping {
         channel_id: ChannelId,
@@ -335,7 +391,7 @@ pub enum Route<'a> {
     CreateReaction {
         channel_id: ChannelId,
         message_id: MessageId,
-        emoji: Emoji,
+        emoji: &'a PartialEmoji,
     },
     CreateRole {
         guild_id: GuildId,
@@ -371,12 +427,12 @@ pub enum Route<'a> {
     DeleteOwnReaction {
         channel_id: ChannelId,
         message_id: MessageId,
-        emoji: Emoji,
+        emoji: &'a PartialEmoji,
     },
     DeleteReaction {
         channel_id: ChannelId,
         message_id: MessageId,
-        emoji: Emoji,
+        emoji: &'a PartialEmoji,
         user_id: UserId,
     },
     DeleteReactions {
@@ -541,7 +597,7 @@ pub enum Route<'a> {
     GetReactionUsers {
         channel_id: ChannelId,
         message_id: MessageId,
-        emoji: Emoji,
+        emoji: &'a PartialEmoji,
         before: Option<UserId>,
         after: Option<UserId>,
         limit: Option<u8>,
@@ -627,7 +683,7 @@ impl<'a> Route<'a> {
             DeleteIntegration { .. } => Method::Delete,
             DeleteInvite { .. } => Method::Delete,
             DeleteMessage { .. } => Method::Delete,
-            DeleteMessagesBulk { .. } => Method::Delete,
+            DeleteMessagesBulk { .. } => Method::Post,
             DeleteOwnReaction { .. } => Method::Delete,
             DeleteReaction { .. } => Method::Delete,
             DeleteReactions { .. } => Method::Delete,
@@ -695,6 +751,16 @@ impl<'a> Route<'a> {
         }
     }
 
+    /// Returns whether requests to the route are sent even while the invalid
+    /// request circuit breaker is open, as they are needed to connect to the
+    /// gateway.
+    pub(crate) fn is_essential(&self) -> bool {
+        match self {
+            Route::GetBotGateway | Route::GetGateway => true,
+            _ => false,
+        }
+    }
+
     pub(crate) fn bucket(&self) -> Bucket {
         use self::Route::*;
 
@@ -912,7 +978,7 @@ impl<'a> Route<'a> {
                 "/channels/{}/messages/{}/reactions/{}/@me",
                 channel_id,
                 message_id,
-                emoji,
+                EncodedEmoji(emoji),
             )),
 
             DeleteReaction {
@@ -924,7 +990,7 @@ impl<'a> Route<'a> {
                 "/channels/{}/messages/{}/reactions/{}/{}",
                 channel_id,
                 message_id,
-                emoji,
+                EncodedEmoji(emoji),
                 user_id,
             )),
 
@@ -939,7 +1005,7 @@ impl<'a> Route<'a> {
                 "/channels/{}/messages/{}/reactions/{}",
                 channel_id,
                 message_id,
-                emoji;
+                EncodedEmoji(emoji);
                 [
                     ("before", before?),
                     ("after", after?),
@@ -1071,10 +1137,8 @@ impl<'a> Route<'a> {
                 guild_id,
                 user_id,
                 delete_message_days,
-                reason,
             } => Cow::from(api!("/guilds/{}/bans/{}", guild_id, user_id; [
                 ("delete-message-days", delete_message_days?),
-                ("reason", reason?),
             ])),
 
             GetRoles { guild_id } | CreateRole { guild_id } | EditRolePositions { guild_id } => {
@@ -1144,7 +1208,7 @@ impl<'a> Route<'a> {
 
             GetUser { user_id } => Cow::from(api!("/users/{}", user_id)),
 
-            LeaveGuild { guild_id } => Cow::from(api!("/user/@me/guilds/{}", guild_id)),
+            LeaveGuild { guild_id } => Cow::from(api!("/users/@me/guilds/{}", guild_id)),
 
             CreatePrivateChannel => Cow::from(api!("/users/@me/channels")),
 
@@ -1180,14 +1244,24 @@ impl<'a> Route<'a> {
     }
 }
 
-/// Methods implementing `Copy`, with mappings to corresponding reqwest methods.
+/// The HTTP method of a route.
+///
+/// Routes in the same [`Bucket`] may have different ratelimits depending on
+/// their method.
+///
+/// [`Bucket`]: enum.Bucket.html
 #[remain::sorted]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Method {
+    /// The `DELETE` method.
     Delete,
+    /// The `GET` method.
     Get,
+    /// The `PATCH` method.
     Patch,
+    /// The `POST` method.
     Post,
+    /// The `PUT` method.
     Put,
 }
 
@@ -1203,9 +1277,101 @@ impl From<Method> for HttpMethod {
     }
 }
 
+/// The position of messages to get, relative to a message.
 #[derive(Clone, Copy, Debug)]
 pub enum AroundMessage {
+    /// Messages around the message.
     Around(MessageId),
+    /// Messages before the message.
     Before(MessageId),
+    /// Messages after the message.
     After(MessageId),
 }
+
+/// Formats an emoji for use in the path of a URL.
+///
+/// Standard emojis are percent-encoded, and custom emojis are formatted as
+/// `name:id`, with the name percent-encoded.
+struct EncodedEmoji<'a>(&'a PartialEmoji);
+
+impl Display for EncodedEmoji<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            PartialEmoji::Standard(name) => percent_encode(f, name),
+            PartialEmoji::Custom(emoji) => {
+                // The name of a custom emoji is ignored by Discord, but must be
+                // present.
+                percent_encode(f, emoji.name.as_deref().unwrap_or("_"))?;
+                write!(f, ":{}", emoji.id)
+            }
+        }
+    }
+}
+
+/// Writes `s` with all bytes except unreserved characters percent-encoded.
+pub(crate) fn percent_encode<W: fmt::Write>(f: &mut W, s: &str) -> fmt::Result {
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                f.write_char(byte as char)?
+            }
+            _ => write!(f, "%{:02X}", byte)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reaction_url_standard() {
+        let emoji = PartialEmoji::standard("👍");
+        let route = Route::CreateReaction {
+            channel_id: ChannelId::from(1),
+            message_id: MessageId::from(2),
+            emoji: &emoji,
+        };
+
+        assert_eq!(
+            route.url(),
+            api!("/channels/1/messages/2/reactions/%F0%9F%91%8D/@me")
+        );
+    }
+
+    #[test]
+    fn test_reaction_url_custom() {
+        let emoji = PartialEmoji::custom(41771983429993937, "LUL", true);
+        let route = Route::DeleteReaction {
+            channel_id: ChannelId::from(1),
+            message_id: MessageId::from(2),
+            emoji: &emoji,
+            user_id: UserId::from(3),
+        };
+
+        assert_eq!(
+            route.url(),
+            api!("/channels/1/messages/2/reactions/LUL:41771983429993937/3")
+        );
+    }
+
+    #[test]
+    fn test_reaction_url_query() {
+        let emoji = PartialEmoji::standard("🔥");
+        let route = Route::GetReactionUsers {
+            channel_id: ChannelId::from(1),
+            message_id: MessageId::from(2),
+            emoji: &emoji,
+            before: None,
+            after: Some(UserId::from(3)),
+            limit: Some(10),
+        };
+
+        let url = route.url();
+        assert!(url.starts_with(api!("/channels/1/messages/2/reactions/%F0%9F%94%A5?")));
+        assert!(url.contains("after=3"));
+        assert!(url.contains("limit=10"));
+        assert!(!url.contains("before"));
+    }
+}
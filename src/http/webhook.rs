@@ -0,0 +1,253 @@
+use serde::Serialize;
+
+use crate::builder::ExecuteWebhook;
+use crate::internal::prelude::*;
+use crate::model::channel::Message;
+use crate::model::id::WebhookId;
+use crate::model::webhook::Webhook;
+
+use super::prelude::*;
+use super::Http;
+
+/// The hosts webhook URLs are accepted from.
+const WEBHOOK_HOSTS: &[&str] = &[
+    "discordapp.com",
+    "canary.discordapp.com",
+    "ptb.discordapp.com",
+    "discord.com",
+    "canary.discord.com",
+    "ptb.discord.com",
+];
+
+/// A client for executing a single webhook, authenticated by the token of the
+/// webhook instead of a bot token.
+///
+/// # Examples
+///
+/// ```no_run
+/// use strife::builder::ExecuteWebhook;
+/// use strife::http::WebhookClient;
+///
+/// # async fn run() -> strife::Result<()> {
+/// let client = WebhookClient::from_url("https://discordapp.com/api/webhooks/223704706495545344/3d89bb7572e0fb30d8128367b3b1b44fecd1726de135cbe28a41f8b2f777c372ba2939e72279b94526ff5d1bd4358d65cf11")?;
+///
+/// let mut message = ExecuteWebhook::new();
+/// message.content("Build passed").username("CI");
+///
+/// client.execute(&message, false).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct WebhookClient {
+    http: Http,
+    id: WebhookId,
+    token: String,
+}
+
+impl WebhookClient {
+    /// Creates a client for the webhook with the given ID and token.
+    pub fn new<S: Into<String>>(id: WebhookId, token: S) -> WebhookClient {
+        WebhookClient::with_http(Http::without_token(), id, token)
+    }
+
+    /// Creates a client for the webhook with the given ID and token, that
+    /// sends requests with the given HTTP client.
+    ///
+    /// This allows using a client built by an [`HttpBuilder`], eg. with a
+    /// custom base URL. The HTTP client does not need an API token.
+    ///
+    /// [`HttpBuilder`]: struct.HttpBuilder.html
+    pub fn with_http<S: Into<String>>(http: Http, id: WebhookId, token: S) -> WebhookClient {
+        WebhookClient {
+            http,
+            id,
+            token: token.into(),
+        }
+    }
+
+    /// Creates a client from a webhook URL, of the form
+    /// `https://discordapp.com/api/webhooks/{id}/{token}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidWebhookUrl`] error if the URL is not a webhook URL.
+    ///
+    /// [`InvalidWebhookUrl`]: enum.HttpError.html#variant.InvalidWebhookUrl
+    pub fn from_url(url: &str) -> Result<WebhookClient> {
+        match parse_url(url) {
+            Some((id, token)) => Ok(WebhookClient::new(id, token)),
+            None => Err(HttpError::InvalidWebhookUrl(url.to_owned()).into()),
+        }
+    }
+
+    /// The ID of the webhook.
+    pub fn id(&self) -> WebhookId {
+        self.id
+    }
+
+    /// The token of the webhook.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Executes the webhook.
+    ///
+    /// The message is validated before it is sent. If `wait` is `true` the
+    /// created message is returned, otherwise the request completes before the
+    /// message is created and `None` is returned.
+    pub async fn execute(&self, message: &ExecuteWebhook, wait: bool) -> Result<Option<Message>> {
+        self.execute_with_files(message, wait, Vec::new()).await
+    }
+
+    /// Executes the webhook with attached files.
+    ///
+    /// The message is validated before it is sent, and can be empty if any
+    /// files are attached. If `wait` is `true` the
+    /// created message is returned, otherwise the request completes before the
+    /// message is created and `None` is returned.
+    pub async fn execute_with_files(
+        &self,
+        message: &ExecuteWebhook,
+        wait: bool,
+        files: Vec<FileUpload>,
+    ) -> Result<Option<Message>> {
+        self.http
+            .execute_webhook(self.id, &self.token, wait, message, files)
+            .await
+    }
+
+    /// Gets the webhook.
+    pub async fn get(&self) -> Result<Webhook> {
+        self.http.get_webhook_with_token(self.id, &self.token).await
+    }
+
+    /// Edits the default name and avatar of the webhook.
+    pub async fn edit<B>(&self, body: &B) -> Result<Webhook>
+    where
+        B: ?Sized + Serialize,
+    {
+        self.http
+            .edit_webhook_with_token(self.id, &self.token, body)
+            .await
+    }
+
+    /// Deletes the webhook.
+    pub async fn delete(&self) -> Result<()> {
+        self.http
+            .delete_webhook_with_token(self.id, &self.token)
+            .await
+    }
+}
+
+/// Parses the ID and token from a webhook URL.
+fn parse_url(url: &str) -> Option<(WebhookId, &str)> {
+    let url = strip_prefix(url, "https://")?;
+
+    let (host, path) = url.split_at(url.find('/')?);
+    if !WEBHOOK_HOSTS.contains(&host) {
+        return None;
+    }
+
+    let mut path = strip_prefix(path, "/api/")?;
+    // Skip an optional API version, eg. `v6/`.
+    if let Some(rest) = strip_prefix(path, "v") {
+        let end = rest.find('/')?;
+        if end == 0 || !rest[..end].bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        path = &rest[end + 1..];
+    }
+
+    let path = strip_prefix(path, "webhooks/")?;
+    let path = if path.ends_with('/') {
+        &path[..path.len() - 1]
+    } else {
+        path
+    };
+
+    let (id, token) = path.split_at(path.find('/')?);
+    let token = &token[1..];
+
+    let id = id.parse::<u64>().ok()?;
+    let valid_token = token
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if token.is_empty() || !valid_token {
+        return None;
+    }
+
+    Some((WebhookId::from(id), token))
+}
+
+/// Returns the string without the given prefix, if it starts with it.
+fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    if s.starts_with(prefix) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: u64 = 223704706495545344;
+    const TOKEN: &str = "3d89bb7572e0fb30d8128367b3b1b44fecd1726de135cbe28a41f8b2f777c372ba2939e72279b94526ff5d1bd4358d65cf11";
+
+    #[test]
+    fn test_parse_url() {
+        let urls = [
+            format!("https://discordapp.com/api/webhooks/{}/{}", ID, TOKEN),
+            format!("https://discord.com/api/webhooks/{}/{}/", ID, TOKEN),
+            format!(
+                "https://canary.discordapp.com/api/v6/webhooks/{}/{}",
+                ID, TOKEN
+            ),
+        ];
+
+        for url in urls.iter() {
+            assert_eq!(
+                parse_url(url),
+                Some((WebhookId::from(ID), TOKEN)),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_url_invalid() {
+        let urls = [
+            format!("http://discordapp.com/api/webhooks/{}/{}", ID, TOKEN),
+            format!("https://example.com/api/webhooks/{}/{}", ID, TOKEN),
+            format!("https://discordapp.com/webhooks/{}/{}", ID, TOKEN),
+            format!("https://discordapp.com/api/vx/webhooks/{}/{}", ID, TOKEN),
+            format!("https://discordapp.com/api/webhooks/{}", ID),
+            format!("https://discordapp.com/api/webhooks/{}/", ID),
+            format!("https://discordapp.com/api/webhooks/abc/{}", TOKEN),
+            format!(
+                "https://discordapp.com/api/webhooks/{}/{}?wait=true",
+                ID, TOKEN
+            ),
+            format!("https://discordapp.com/api/webhooks/{}/{}/slack", ID, TOKEN),
+        ];
+
+        for url in urls.iter() {
+            assert_eq!(parse_url(url), None, "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_from_url() {
+        let url = format!("https://discordapp.com/api/webhooks/{}/{}", ID, TOKEN);
+        let client = WebhookClient::from_url(&url).unwrap();
+        assert_eq!(client.id(), WebhookId::from(ID));
+        assert_eq!(client.token(), TOKEN);
+
+        match WebhookClient::from_url("https://discordapp.com/api/webhooks") {
+            Err(Error::HttpError(HttpError::InvalidWebhookUrl(_))) => {}
+            _ => panic!("expected invalid webhook URL error"),
+        }
+    }
+}
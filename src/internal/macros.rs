@@ -113,6 +113,97 @@ macro_rules! wrap {
     };
 }
 
+/// Defines an integer enum with an additional `Unknown` variant, which retains
+/// values that are not known to the library instead of failing to deserialize.
+macro_rules! int_enum {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident: $int:ty {
+            $(
+                $(#[$variant_attr:meta])*
+                $variant:ident = $value:literal,
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $(
+                $(#[$variant_attr])*
+                $variant,
+            )*
+            /// A value that is not known to the library.
+            Unknown($int),
+        }
+
+        impl ::std::convert::From<$int> for $name {
+            fn from(value: $int) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    value => $name::Unknown(value),
+                }
+            }
+        }
+
+        impl ::std::convert::From<$name> for $int {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl ::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                <$int as ::serde::Serialize>::serialize(&(*self).into(), serializer)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                <$int as ::serde::Deserialize>::deserialize(deserializer).map($name::from)
+            }
+        }
+    };
+}
+
+/// Implements `Serialize` and `Deserialize` for a `bitflags` type as its bits,
+/// deserializing the bits with the given visitor.
+///
+/// Bits that are not known to the library are kept rather than rejected, so
+/// new flags added by Discord do not cause deserialization to fail, and are
+/// sent back unchanged when the value is serialized.
+macro_rules! bitflags_serde {
+    ($name:ident: $visitor:path) => {
+        impl ::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                ::serde::Serialize::serialize(&self.bits(), serializer)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let bits = deserializer.deserialize_any($visitor)?;
+                // Safety: the flags are plain integers, for which any bits
+                // are valid.
+                Ok(unsafe { $name::from_bits_unchecked(bits) })
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     const ID: u64 = 80351110224678912;
@@ -8,8 +8,10 @@ mod error;
 #[macro_use]
 mod internal;
 
+pub mod builder;
 pub mod client;
 pub mod constants;
+pub mod gateway;
 pub mod http;
 pub mod model;
 
@@ -4,11 +4,13 @@ mod category;
 mod news_channel;
 mod store_channel;
 mod text_channel;
+mod unknown_channel;
 mod voice_channel;
 
 use serde::de;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::model::channel::permissions::PermissionOverwrite;
 use crate::model::channel::ChannelType;
 use crate::model::id::{ChannelId, ToSnowflakeId};
 
@@ -16,6 +18,7 @@ pub use self::category::Category;
 pub use self::news_channel::NewsChannel;
 pub use self::store_channel::StoreChannel;
 pub use self::text_channel::TextChannel;
+pub use self::unknown_channel::UnknownChannel;
 pub use self::voice_channel::VoiceChannel;
 
 /// A channel in a [`Guild`].
@@ -40,6 +43,8 @@ pub enum GuildChannel {
     News(NewsChannel),
     /// A channel in which game developers can sell games on Discord.
     Store(StoreChannel),
+    /// A channel of a type that is not known to the library.
+    Unknown(UnknownChannel),
 }
 
 impl GuildChannel {
@@ -51,6 +56,20 @@ impl GuildChannel {
             GuildChannel::Category(_) => ChannelType::Category,
             GuildChannel::News(_) => ChannelType::News,
             GuildChannel::Store(_) => ChannelType::Store,
+            GuildChannel::Unknown(channel) => channel.kind,
+        }
+    }
+
+    /// The explicit permission overwrites for members and roles in the
+    /// channel.
+    pub fn permission_overwrites(&self) -> &[PermissionOverwrite] {
+        match self {
+            GuildChannel::Text(channel) => &channel.permission_overwrites,
+            GuildChannel::Voice(channel) => &channel.permission_overwrites,
+            GuildChannel::Category(channel) => &channel.permission_overwrites,
+            GuildChannel::News(channel) => &channel.permission_overwrites,
+            GuildChannel::Store(channel) => &channel.permission_overwrites,
+            GuildChannel::Unknown(channel) => &channel.permission_overwrites,
         }
     }
 }
@@ -69,6 +88,7 @@ impl ToSnowflakeId for GuildChannel {
             GuildChannel::Category(channel) => channel.id,
             GuildChannel::News(channel) => channel.id,
             GuildChannel::Store(channel) => channel.id,
+            GuildChannel::Unknown(channel) => channel.id,
         }
     }
 }
@@ -87,6 +107,9 @@ impl GuildChannel {
             ChannelType::Category => Category::deserialize(value).map(GuildChannel::Category),
             ChannelType::News => NewsChannel::deserialize(value).map(GuildChannel::News),
             ChannelType::Store => StoreChannel::deserialize(value).map(GuildChannel::Store),
+            ChannelType::Unknown(_) => {
+                UnknownChannel::deserialize(value).map(GuildChannel::Unknown)
+            }
             kind => {
                 return Err(E::custom(format_args!(
                     "invalid channel type for guild channel: {:?}",
@@ -109,6 +132,7 @@ impl Serialize for GuildChannel {
             GuildChannel::Category(channel) => channel.serialize(serializer),
             GuildChannel::News(channel) => channel.serialize(serializer),
             GuildChannel::Store(channel) => channel.serialize(serializer),
+            GuildChannel::Unknown(channel) => channel.serialize(serializer),
         }
     }
 }
@@ -135,6 +159,7 @@ impl_eq_fields!(GuildChannel: (a, b) => {
         (GuildChannel::Category(a), GuildChannel::Category(b)) => assert_eq_fields!(a, b),
         (GuildChannel::News(a), GuildChannel::News(b)) => assert_eq_fields!(a, b),
         (GuildChannel::Store(a), GuildChannel::Store(b)) => assert_eq_fields!(a, b),
+        (GuildChannel::Unknown(a), GuildChannel::Unknown(b)) => assert_eq_fields!(a, b),
         (a, b) => panic_ne_fields!(a, b),
     }
 });
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::model::channel::permissions::PermissionOverwrite;
+use crate::model::channel::ChannelType;
+use crate::model::id::{ChannelId, GuildId};
+
+/// A channel in a [`Guild`] of a type that is not known to the library.
+///
+/// The fields common to all guild channels are parsed, the remaining fields
+/// are retained as raw JSON.
+///
+/// [`Guild`]: ../../guild/struct.Guild.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UnknownChannel {
+    /// The ID of the channel.
+    pub id: ChannelId,
+    /// The type of the channel.
+    ///
+    /// This should always be [`ChannelType::Unknown`].
+    ///
+    /// [`ChannelType::Unknown`]: ../enum.ChannelType.html#variant.Unknown
+    #[serde(rename = "type")]
+    pub(crate) kind: ChannelType,
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// A collection of explicit permission overwrites for members and roles.
+    #[serde(default)]
+    pub permission_overwrites: Vec<PermissionOverwrite>,
+    /// The remaining fields of the channel.
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+impl_eq_fields!(UnknownChannel: [id, kind, guild_id, permission_overwrites, fields]);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::model::channel::{Channel, GuildChannel};
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let value = json!({
+          "permission_overwrites": [],
+          "name": "forum",
+          "position": 3,
+          "guild_id": "290926798629997250",
+          "type": 15,
+          "id": "399942396007890945"
+        });
+        let mut fields = Map::new();
+        fields.insert("name".to_owned(), json!("forum"));
+        fields.insert("position".to_owned(), json!(3));
+        let channel = UnknownChannel {
+            id: ChannelId::from(399942396007890945),
+            kind: ChannelType::Unknown(15),
+            guild_id: Some(GuildId::from(290926798629997250)),
+            permission_overwrites: vec![],
+            fields,
+        };
+
+        let deserialized = UnknownChannel::deserialize(&value).unwrap();
+        assert_eq_fields!(channel, deserialized);
+
+        let channel = Channel::Guild(GuildChannel::Unknown(channel));
+        let deserialized = Channel::deserialize(&value).unwrap();
+        assert_eq_fields!(channel, deserialized);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let value = json!({
+          "permission_overwrites": [],
+          "name": "forum",
+          "position": 3,
+          "guild_id": "290926798629997250",
+          "type": 15,
+          "id": "399942396007890945"
+        });
+        let channel = UnknownChannel::deserialize(&value).unwrap();
+
+        assert_eq!(value, serde_json::to_value(&channel).unwrap());
+    }
+}
@@ -4,7 +4,7 @@ use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 
 use crate::model::color::Color;
-use crate::model::utils::is_false;
+use crate::model::utils::{is_false, is_zero};
 
 /// Embedded content in a [`Message`].
 ///
@@ -109,6 +109,23 @@ pub struct EmbedFooter {
     pub proxy_icon_url: Option<String>,
 }
 
+impl EmbedFooter {
+    /// Creates footer information with the given text.
+    pub fn new<S: Into<String>>(text: S) -> EmbedFooter {
+        EmbedFooter {
+            text: text.into(),
+            icon_url: None,
+            proxy_icon_url: None,
+        }
+    }
+
+    /// Sets the URL of the footer icon.
+    pub fn icon_url<S: Into<String>>(mut self, icon_url: S) -> Self {
+        self.icon_url = Some(icon_url.into());
+        self
+    }
+}
+
 /// Image information in an [`Embed`].
 ///
 /// [`Embed`]: struct.Embed.html
@@ -120,13 +137,28 @@ pub struct EmbedImage {
     /// Only supports HTTP(S).
     pub url: String,
     /// The proxied URL of the image.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub proxy_url: String,
     /// The height of the image.
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub height: u64,
     /// The width of the image.
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub width: u64,
 }
 
+impl EmbedImage {
+    /// Creates image information with the given URL.
+    pub fn new<S: Into<String>>(url: S) -> EmbedImage {
+        EmbedImage {
+            url: url.into(),
+            proxy_url: String::new(),
+            height: 0,
+            width: 0,
+        }
+    }
+}
+
 /// Thumbnail information in an [`Embed`].
 ///
 /// [`Embed`]: struct.Embed.html
@@ -138,13 +170,28 @@ pub struct EmbedThumbnail {
     /// Only supports HTTP(S).
     pub url: String,
     /// The proxied URL of the thumbnail.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub proxy_url: String,
     /// The height of the thumbnail.
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub height: u64,
     /// The width of the thumbnail.
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub width: u64,
 }
 
+impl EmbedThumbnail {
+    /// Creates thumbnail information with the given URL.
+    pub fn new<S: Into<String>>(url: S) -> EmbedThumbnail {
+        EmbedThumbnail {
+            url: url.into(),
+            proxy_url: String::new(),
+            height: 0,
+            width: 0,
+        }
+    }
+}
+
 /// Video information in an [`Embed`].
 ///
 /// [`Embed`]: struct.Embed.html
@@ -193,6 +240,30 @@ pub struct EmbedAuthor {
     pub proxy_icon_url: Option<String>,
 }
 
+impl EmbedAuthor {
+    /// Creates author information with the given name.
+    pub fn new<S: Into<String>>(name: S) -> EmbedAuthor {
+        EmbedAuthor {
+            name: name.into(),
+            url: None,
+            icon_url: None,
+            proxy_icon_url: None,
+        }
+    }
+
+    /// Sets the URL of the author.
+    pub fn url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the URL of the author icon.
+    pub fn icon_url<S: Into<String>>(mut self, icon_url: S) -> Self {
+        self.icon_url = Some(icon_url.into());
+        self
+    }
+}
+
 /// A field in an [`Embed`].
 ///
 /// [`Embed`]: struct.Embed.html
@@ -207,3 +278,18 @@ pub struct EmbedField {
     #[serde(default, skip_serializing_if = "is_false")]
     pub inline: bool,
 }
+
+impl EmbedField {
+    /// Creates a field with the given name and value.
+    pub fn new<N, V>(name: N, value: V, inline: bool) -> EmbedField
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        EmbedField {
+            name: name.into(),
+            value: value.into(),
+            inline,
+        }
+    }
+}
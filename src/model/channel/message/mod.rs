@@ -7,8 +7,7 @@ mod rich_presence;
 
 use bitflags::bitflags;
 use chrono::{DateTime, FixedOffset};
-use serde::de;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Deserialize, Serialize};
 
 use crate::model::channel::ChannelType;
 use crate::model::guild::PartialEmoji;
@@ -111,28 +110,29 @@ pub struct Message {
     pub flags: MessageFlags,
 }
 
-/// Type of a [`Message`].
-///
-/// [`Message`]: struct.Message.html
-// TODO: Add docs.
-#[allow(missing_docs)]
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub enum MessageType {
-    Default = 0,
-    RecipientAdd = 1,
-    RecipientRemove = 2,
-    Call = 3,
-    ChannelNameChange = 4,
-    ChannelIconChange = 5,
-    ChannelPinnedMessage = 6,
-    GuildMemberJoin = 7,
-    UserPremiumGuildSubscription = 8,
-    UserPremiumGuildSubscriptionTier1 = 9,
-    UserPremiumGuildSubscriptionTier2 = 10,
-    UserPremiumGuildSubscriptionTier3 = 11,
-    ChannelFollowAdd = 12,
+int_enum! {
+    /// Type of a [`Message`].
+    ///
+    /// [`Message`]: struct.Message.html
+    // TODO: Add docs.
+    #[allow(missing_docs)]
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum MessageType: u8 {
+        Default = 0,
+        RecipientAdd = 1,
+        RecipientRemove = 2,
+        Call = 3,
+        ChannelNameChange = 4,
+        ChannelIconChange = 5,
+        ChannelPinnedMessage = 6,
+        GuildMemberJoin = 7,
+        UserPremiumGuildSubscription = 8,
+        UserPremiumGuildSubscriptionTier1 = 9,
+        UserPremiumGuildSubscriptionTier2 = 10,
+        UserPremiumGuildSubscriptionTier3 = 11,
+        ChannelFollowAdd = 12,
+    }
 }
 
 impl Default for MessageType {
@@ -207,33 +207,7 @@ bitflags! {
     }
 }
 
-impl Serialize for MessageFlags {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u8(self.bits())
-    }
-}
-
-impl<'de> Deserialize<'de> for MessageFlags {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let bits = deserializer.deserialize_any(U8Visitor)?;
-        match MessageFlags::from_bits(bits) {
-            Some(perms) => Ok(perms),
-            None => {
-                let unknown: u8 = bits & !MessageFlags::all().bits();
-                Err(de::Error::custom(format!(
-                    "unknown user flags bits {:b} in {:b}",
-                    unknown, bits
-                )))
-            }
-        }
-    }
-}
+bitflags_serde!(MessageFlags: U8Visitor);
 
 /// A reaction to a [`Message`].
 ///
@@ -626,4 +600,23 @@ mod tests {
 
         assert_eq!(value, serde_json::to_value(&message).unwrap());
     }
+
+    #[test]
+    fn test_unknown_message_type() {
+        let kind = MessageType::deserialize(&json!(19)).unwrap();
+        assert_eq!(kind, MessageType::Unknown(19));
+        assert_eq!(json!(19), serde_json::to_value(&kind).unwrap());
+
+        let kind = MessageType::deserialize(&json!(7)).unwrap();
+        assert_eq!(kind, MessageType::GuildMemberJoin);
+    }
+
+    #[test]
+    fn test_unknown_message_flags() {
+        let value = json!(0b1000_0010);
+        let flags = MessageFlags::deserialize(&value).unwrap();
+        assert!(flags.contains(MessageFlags::IS_CROSSPOST));
+        assert_eq!(flags.bits(), 0b1000_0010);
+        assert_eq!(value, serde_json::to_value(&flags).unwrap());
+    }
 }
@@ -12,18 +12,19 @@ pub struct MessageActivity {
     pub party_id: Option<String>,
 }
 
-/// Type of a [`MessageActivity`].
-///
-/// [`Message`]: struct.MessageActivity.html
-#[allow(missing_docs)]
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub enum MessageActivityType {
-    Join = 1,
-    Spectate = 2,
-    Listen = 3,
-    JoinRequest = 5,
+int_enum! {
+    /// Type of a [`MessageActivity`].
+    ///
+    /// [`Message`]: struct.MessageActivity.html
+    #[allow(missing_docs)]
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum MessageActivityType: u8 {
+        Join = 1,
+        Spectate = 2,
+        Listen = 3,
+        JoinRequest = 5,
+    }
 }
 
 /// Rich Presence application information.
@@ -16,25 +16,26 @@ pub use self::guild::GuildChannel;
 pub use self::message::Message;
 use crate::model::id::{ChannelId, ToSnowflakeId};
 
-/// The type of a channel.
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub enum ChannelType {
-    /// A text channel in a guild.
-    Text = 0,
-    /// A direct message channel between the client user and another user.
-    Private = 1,
-    /// A voice channel in a guild.
-    Voice = 2,
-    /// A group message channel between multiple users.
-    Group = 3,
-    /// An organizational category that contains non-category channels.
-    Category = 4,
-    /// A channel that users can follow and crosspost into another guild.
-    News = 5,
-    /// A channel in which game developers can sell games on Discord.
-    Store = 6,
+int_enum! {
+    /// The type of a channel.
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum ChannelType: u8 {
+        /// A text channel in a guild.
+        Text = 0,
+        /// A direct message channel between the client user and another user.
+        Private = 1,
+        /// A voice channel in a guild.
+        Voice = 2,
+        /// A group message channel between multiple users.
+        Group = 3,
+        /// An organizational category that contains non-category channels.
+        Category = 4,
+        /// A channel that users can follow and crosspost into another guild.
+        News = 5,
+        /// A channel in which game developers can sell games on Discord.
+        Store = 6,
+    }
 }
 
 impl ChannelType {
@@ -133,7 +134,8 @@ impl<'de> Deserialize<'de> for Channel {
             | ChannelType::Voice
             | ChannelType::Category
             | ChannelType::News
-            | ChannelType::Store => GuildChannel::from_value(kind, value).map(Channel::Guild),
+            | ChannelType::Store
+            | ChannelType::Unknown(_) => GuildChannel::from_value(kind, value).map(Channel::Guild),
             ChannelType::Private => DMChannel::deserialize(value)
                 .map(Channel::DM)
                 .map_err(de::Error::custom),
@@ -2,8 +2,7 @@
 
 use bitflags::bitflags;
 use chrono::{DateTime, Utc};
-use serde::de;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Deserialize, Serialize};
 
 use crate::model::id::{ApplicationId, EmojiId};
 use crate::model::utils::{is_false, serde_option_timestamp, U8Visitor};
@@ -24,30 +23,31 @@ pub struct ActivityTimestamps {
     pub end: Option<DateTime<Utc>>,
 }
 
-/// The type of an [`Activity`].
-///
-/// [`Activity`]: struct.Activity.html
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum ActivityType {
-    /// Format: `Playing {`[`name`]`}`.
-    ///
-    /// [`name`]: struct.Activity.html#structfield.name
-    Game = 0,
-    /// Format: `Streaming {`[`details`]`}`.
-    ///
-    /// [`details`]: struct.Activity.html#structfield.details
-    Streaming = 1,
-    /// Format: `Listening to {`[`name`]`}`.
-    ///
-    /// [`name`]: struct.Activity.html#structfield.name
-    Listening = 2,
-    /// Format: `{`[`emoji`]`} {`[`name`]`}`.
+int_enum! {
+    /// The type of an [`Activity`].
     ///
-    /// [`emoji`]: struct.Activity.html#structfield.emoji
-    /// [`name`]: struct.Activity.html#structfield.name
-    Custom = 3,
+    /// [`Activity`]: struct.Activity.html
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum ActivityType: u8 {
+        /// Format: `Playing {`[`name`]`}`.
+        ///
+        /// [`name`]: struct.Activity.html#structfield.name
+        Game = 0,
+        /// Format: `Streaming {`[`details`]`}`.
+        ///
+        /// [`details`]: struct.Activity.html#structfield.details
+        Streaming = 1,
+        /// Format: `Listening to {`[`name`]`}`.
+        ///
+        /// [`name`]: struct.Activity.html#structfield.name
+        Listening = 2,
+        /// Format: `{`[`emoji`]`} {`[`name`]`}`.
+        ///
+        /// [`emoji`]: struct.Activity.html#structfield.emoji
+        /// [`name`]: struct.Activity.html#structfield.name
+        Custom = 3,
+    }
 }
 
 /// An emoji in a [`Custom`] status for an [`Activity`]
@@ -154,33 +154,7 @@ bitflags! {
     }
 }
 
-impl Serialize for ActivityFlags {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u8(self.bits())
-    }
-}
-
-impl<'de> Deserialize<'de> for ActivityFlags {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let bits = deserializer.deserialize_any(U8Visitor)?;
-        match ActivityFlags::from_bits(bits) {
-            Some(perms) => Ok(perms),
-            None => {
-                let unknown: u8 = bits & !ActivityFlags::all().bits();
-                Err(de::Error::custom(format!(
-                    "unknown permissions bits {:b} in {:b}",
-                    unknown, bits
-                )))
-            }
-        }
-    }
-}
+bitflags_serde!(ActivityFlags: U8Visitor);
 
 /// An activity.
 #[non_exhaustive]
@@ -0,0 +1,608 @@
+//! Models of events dispatched by the gateway.
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::channel::message::embed::Embed;
+use crate::model::channel::message::{Attachment, MentionedUser, MessageFlags};
+use crate::model::channel::{Channel, Message};
+use crate::model::gateway::presence::Presence;
+use crate::model::guild::{
+    Emoji, Guild, Member, PartialEmoji, PartialMember, Role, UnavailableGuild,
+};
+use crate::model::id::{ChannelId, GuildId, MessageId, RoleId, UserId};
+use crate::model::user::{ClientUser, User};
+use crate::model::voice::VoiceState;
+
+/// The initial state sent by the gateway after identifying.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReadyEvent {
+    /// The gateway protocol version.
+    #[serde(rename = "v")]
+    pub version: u8,
+    /// The client user.
+    pub user: ClientUser,
+    /// The direct message channels of the client user.
+    #[serde(default)]
+    pub private_channels: Vec<Channel>,
+    /// The guilds the client user is in.
+    ///
+    /// The guilds will be sent in later `GUILD_CREATE` events, once they are
+    /// available.
+    pub guilds: Vec<UnavailableGuild>,
+    /// The ID of the session, used for resuming.
+    pub session_id: String,
+    /// The shard information associated with the session, if sent when
+    /// identifying.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard: Option<[u64; 2]>,
+}
+
+/// Sent when the session has been successfully resumed.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ResumedEvent {}
+
+/// Sent when a message is pinned or unpinned in a text channel.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChannelPinsUpdateEvent {
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+    /// When the most recent pinned message was pinned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_pin_timestamp: Option<DateTime<FixedOffset>>,
+}
+
+/// Sent when a user is banned from, or unbanned from, a guild.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildBanEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The user that was banned or unbanned.
+    pub user: User,
+}
+
+/// Sent when the emojis of a guild have been updated.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildEmojisUpdateEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The emojis of the guild.
+    pub emojis: Vec<Emoji>,
+}
+
+/// Sent when the integrations of a guild have been updated.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildIntegrationsUpdateEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+}
+
+/// Sent when a user joins a guild.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildMemberAddEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The member that joined the guild.
+    #[serde(flatten)]
+    pub member: Member,
+}
+
+/// Sent when a user is removed from a guild (leave/kick/ban).
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildMemberRemoveEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The user that was removed.
+    pub user: User,
+}
+
+/// Sent when a member of a guild is updated.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildMemberUpdateEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The roles of the member.
+    pub roles: Vec<RoleId>,
+    /// The user the member represents.
+    pub user: User,
+    /// The nickname of the member, if one is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+    /// When the user used their Nitro boost on the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub premium_since: Option<DateTime<FixedOffset>>,
+}
+
+/// Sent in response to a request for guild members.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildMembersChunkEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The members in the chunk.
+    pub members: Vec<Member>,
+    /// The IDs of the requested members that were not found.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<UserId>,
+    /// The presences of the members, if requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub presences: Vec<Presence>,
+}
+
+/// Sent when a role is created in, or updated in, a guild.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildRoleEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The role that was created or updated.
+    pub role: Role,
+}
+
+/// Sent when a role is deleted from a guild.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildRoleDeleteEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The ID of the role.
+    pub role_id: RoleId,
+}
+
+/// Sent when a message is updated.
+///
+/// Unlike `MESSAGE_CREATE`, the message may contain only partial information,
+/// fields that were not sent are `None`.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageUpdateEvent {
+    /// The ID of the message.
+    pub id: MessageId,
+    /// The ID of the channel the message was sent in.
+    pub channel_id: ChannelId,
+    /// The ID of the guild the message was sent in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// The author of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<User>,
+    /// The contents of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// When the message was edited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_timestamp: Option<DateTime<FixedOffset>>,
+    /// Whether the message was a Text-To-Speech message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    /// Whether the message mentions everyone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mention_everyone: Option<bool>,
+    /// The users specifically mentioned in the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mentions: Option<Vec<MentionedUser>>,
+    /// The roles specifically mentioned in the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mention_roles: Option<Vec<RoleId>>,
+    /// The files attached to the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+    /// The embedded content of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// Whether the message is pinned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+    /// The message flags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+}
+
+/// Sent when a message is deleted.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageDeleteEvent {
+    /// The ID of the message.
+    pub id: MessageId,
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+}
+
+/// Sent when multiple messages are deleted at once.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageDeleteBulkEvent {
+    /// The IDs of the messages.
+    pub ids: Vec<MessageId>,
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+}
+
+/// Sent when a user adds a reaction to, or removes a reaction from, a message.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReactionEvent {
+    /// The ID of the user.
+    pub user_id: UserId,
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+    /// The ID of the message.
+    pub message_id: MessageId,
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// The member that reacted, if the reaction was added in a guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<Member>,
+    /// The emoji used to react.
+    pub emoji: PartialEmoji,
+}
+
+/// Sent when a user explicitly removes all reactions from a message.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReactionRemoveAllEvent {
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+    /// The ID of the message.
+    pub message_id: MessageId,
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+}
+
+/// Sent when a user removes all reactions of a single emoji from a message.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageReactionRemoveEmojiEvent {
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+    /// The ID of the message.
+    pub message_id: MessageId,
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// The emoji that was removed.
+    pub emoji: PartialEmoji,
+}
+
+/// Sent when a user starts typing in a channel.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TypingStartEvent {
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+    /// The ID of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// The ID of the user.
+    pub user_id: UserId,
+    /// When the user started typing, as a unix timestamp in seconds.
+    pub timestamp: u64,
+    /// The member that started typing, if in a guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<PartialMember>,
+}
+
+/// Sent when the voice server of a guild is updated.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VoiceServerUpdateEvent {
+    /// The voice connection token.
+    pub token: String,
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The voice server host.
+    pub endpoint: Option<String>,
+}
+
+/// Sent when a webhook in a channel is created, updated or deleted.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhooksUpdateEvent {
+    /// The ID of the guild.
+    pub guild_id: GuildId,
+    /// The ID of the channel.
+    pub channel_id: ChannelId,
+}
+
+/// An event that is not known by the library.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct UnknownEvent {
+    /// The name of the event.
+    pub kind: String,
+    /// The raw data of the event.
+    pub data: Value,
+}
+
+/// An event dispatched by the gateway.
+#[non_exhaustive]
+#[remain::sorted]
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// A channel was created.
+    ChannelCreate(Channel),
+    /// A channel was deleted.
+    ChannelDelete(Channel),
+    /// A message was pinned or unpinned in a channel.
+    ChannelPinsUpdate(ChannelPinsUpdateEvent),
+    /// A channel was updated.
+    ChannelUpdate(Channel),
+    /// A user was banned from a guild.
+    GuildBanAdd(GuildBanEvent),
+    /// A user was unbanned from a guild.
+    GuildBanRemove(GuildBanEvent),
+    /// A guild became available, or the client user joined a guild.
+    GuildCreate(Box<Guild>),
+    /// A guild became unavailable, or the client user left or was removed
+    /// from a guild.
+    GuildDelete(UnavailableGuild),
+    /// The emojis of a guild were updated.
+    GuildEmojisUpdate(GuildEmojisUpdateEvent),
+    /// The integrations of a guild were updated.
+    GuildIntegrationsUpdate(GuildIntegrationsUpdateEvent),
+    /// A user joined a guild.
+    GuildMemberAdd(GuildMemberAddEvent),
+    /// A user was removed from a guild.
+    GuildMemberRemove(GuildMemberRemoveEvent),
+    /// A chunk of members requested from a guild.
+    GuildMembersChunk(GuildMembersChunkEvent),
+    /// A member of a guild was updated.
+    GuildMemberUpdate(GuildMemberUpdateEvent),
+    /// A role was created in a guild.
+    GuildRoleCreate(GuildRoleEvent),
+    /// A role was deleted from a guild.
+    GuildRoleDelete(GuildRoleDeleteEvent),
+    /// A role was updated in a guild.
+    GuildRoleUpdate(GuildRoleEvent),
+    /// A guild was updated.
+    GuildUpdate(Box<Guild>),
+    /// A message was created.
+    MessageCreate(Box<Message>),
+    /// A message was deleted.
+    MessageDelete(MessageDeleteEvent),
+    /// Multiple messages were deleted at once.
+    MessageDeleteBulk(MessageDeleteBulkEvent),
+    /// A user reacted to a message.
+    MessageReactionAdd(MessageReactionEvent),
+    /// A user removed a reaction from a message.
+    MessageReactionRemove(MessageReactionEvent),
+    /// All reactions were removed from a message.
+    MessageReactionRemoveAll(MessageReactionRemoveAllEvent),
+    /// All reactions of a single emoji were removed from a message.
+    MessageReactionRemoveEmoji(MessageReactionRemoveEmojiEvent),
+    /// A message was updated.
+    MessageUpdate(Box<MessageUpdateEvent>),
+    /// The presence of a user was updated.
+    PresenceUpdate(Box<Presence>),
+    /// The initial state of the session.
+    Ready(Box<ReadyEvent>),
+    /// The session was resumed.
+    Resumed(ResumedEvent),
+    /// A user started typing in a channel.
+    TypingStart(TypingStartEvent),
+    /// An event not known by the library.
+    Unknown(UnknownEvent),
+    /// The client user was updated.
+    UserUpdate(ClientUser),
+    /// The voice server of a guild was updated.
+    VoiceServerUpdate(VoiceServerUpdateEvent),
+    /// The voice state of a user was updated.
+    VoiceStateUpdate(VoiceState),
+    /// A webhook in a channel was created, updated or deleted.
+    WebhooksUpdate(WebhooksUpdateEvent),
+}
+
+impl Event {
+    /// Deserializes an event from the name and data of a dispatch payload.
+    pub(crate) fn from_dispatch(kind: &str, data: Value) -> serde_json::Result<Event> {
+        use serde_json::from_value;
+
+        Ok(match kind {
+            "CHANNEL_CREATE" => Event::ChannelCreate(from_value(data)?),
+            "CHANNEL_DELETE" => Event::ChannelDelete(from_value(data)?),
+            "CHANNEL_PINS_UPDATE" => Event::ChannelPinsUpdate(from_value(data)?),
+            "CHANNEL_UPDATE" => Event::ChannelUpdate(from_value(data)?),
+            "GUILD_BAN_ADD" => Event::GuildBanAdd(from_value(data)?),
+            "GUILD_BAN_REMOVE" => Event::GuildBanRemove(from_value(data)?),
+            "GUILD_CREATE" => Event::GuildCreate(from_value(data)?),
+            "GUILD_DELETE" => Event::GuildDelete(from_value(data)?),
+            "GUILD_EMOJIS_UPDATE" => Event::GuildEmojisUpdate(from_value(data)?),
+            "GUILD_INTEGRATIONS_UPDATE" => Event::GuildIntegrationsUpdate(from_value(data)?),
+            "GUILD_MEMBER_ADD" => Event::GuildMemberAdd(from_value(data)?),
+            "GUILD_MEMBER_REMOVE" => Event::GuildMemberRemove(from_value(data)?),
+            "GUILD_MEMBER_UPDATE" => Event::GuildMemberUpdate(from_value(data)?),
+            "GUILD_MEMBERS_CHUNK" => Event::GuildMembersChunk(from_value(data)?),
+            "GUILD_ROLE_CREATE" => Event::GuildRoleCreate(from_value(data)?),
+            "GUILD_ROLE_DELETE" => Event::GuildRoleDelete(from_value(data)?),
+            "GUILD_ROLE_UPDATE" => Event::GuildRoleUpdate(from_value(data)?),
+            "GUILD_UPDATE" => Event::GuildUpdate(from_value(data)?),
+            "MESSAGE_CREATE" => Event::MessageCreate(from_value(data)?),
+            "MESSAGE_DELETE" => Event::MessageDelete(from_value(data)?),
+            "MESSAGE_DELETE_BULK" => Event::MessageDeleteBulk(from_value(data)?),
+            "MESSAGE_REACTION_ADD" => Event::MessageReactionAdd(from_value(data)?),
+            "MESSAGE_REACTION_REMOVE" => Event::MessageReactionRemove(from_value(data)?),
+            "MESSAGE_REACTION_REMOVE_ALL" => Event::MessageReactionRemoveAll(from_value(data)?),
+            "MESSAGE_REACTION_REMOVE_EMOJI" => Event::MessageReactionRemoveEmoji(from_value(data)?),
+            "MESSAGE_UPDATE" => Event::MessageUpdate(from_value(data)?),
+            "PRESENCE_UPDATE" => Event::PresenceUpdate(from_value(data)?),
+            "READY" => Event::Ready(from_value(data)?),
+            "RESUMED" => Event::Resumed(ResumedEvent::default()),
+            "TYPING_START" => Event::TypingStart(from_value(data)?),
+            "USER_UPDATE" => Event::UserUpdate(from_value(data)?),
+            "VOICE_SERVER_UPDATE" => Event::VoiceServerUpdate(from_value(data)?),
+            "VOICE_STATE_UPDATE" => Event::VoiceStateUpdate(from_value(data)?),
+            "WEBHOOKS_UPDATE" => Event::WebhooksUpdate(from_value(data)?),
+            _ => Event::Unknown(UnknownEvent {
+                kind: kind.to_owned(),
+                data,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::model::channel::ChannelType;
+    use crate::model::id::ToSnowflakeId;
+
+    use super::*;
+
+    #[test]
+    fn test_ready() {
+        let data = json!({
+            "v": 6,
+            "user": {
+                "id": "80351110224678912",
+                "username": "Nelly",
+                "discriminator": "1337",
+                "avatar": null,
+                "bot": true
+            },
+            "private_channels": [],
+            "guilds": [
+                { "id": "41771983423143937", "unavailable": true }
+            ],
+            "session_id": "a1b2c3d4e5f6",
+            "shard": [0, 1]
+        });
+
+        let ready = match Event::from_dispatch("READY", data).unwrap() {
+            Event::Ready(ready) => ready,
+            event => panic!("unexpected event: {:?}", event),
+        };
+
+        assert_eq!(ready.version, 6);
+        assert_eq!(ready.user.id, UserId::from(80351110224678912));
+        assert_eq!(ready.guilds.len(), 1);
+        assert_eq!(ready.guilds[0].id, GuildId::from(41771983423143937));
+        assert!(ready.guilds[0].unavailable);
+        assert_eq!(ready.session_id, "a1b2c3d4e5f6");
+        assert_eq!(ready.shard, Some([0, 1]));
+    }
+
+    #[test]
+    fn test_channel_create() {
+        let data = json!({
+            "id": "41771983423143937",
+            "guild_id": "41771983423143937",
+            "name": "general",
+            "type": 0,
+            "position": 6,
+            "permission_overwrites": [],
+            "rate_limit_per_user": 2,
+            "nsfw": true,
+            "topic": "24/7 chat about how to gank Mike #2",
+            "last_message_id": "155117677105512449",
+            "parent_id": "399942396007890945"
+        });
+
+        let channel = match Event::from_dispatch("CHANNEL_CREATE", data).unwrap() {
+            Event::ChannelCreate(channel) => channel,
+            event => panic!("unexpected event: {:?}", event),
+        };
+
+        assert_eq!(channel.kind(), ChannelType::Text);
+        assert_eq!(channel.id(), ChannelId::from(41771983423143937));
+    }
+
+    #[test]
+    fn test_guild_member_add() {
+        let data = json!({
+            "guild_id": "41771983423143937",
+            "user": {
+                "id": "80351110224678912",
+                "username": "Nelly",
+                "discriminator": "1337",
+                "avatar": null
+            },
+            "nick": "NOT API SUPPORT",
+            "roles": [],
+            "joined_at": "2015-04-26T06:26:56.936000+00:00",
+            "deaf": false,
+            "mute": false
+        });
+
+        let event = match Event::from_dispatch("GUILD_MEMBER_ADD", data).unwrap() {
+            Event::GuildMemberAdd(event) => event,
+            event => panic!("unexpected event: {:?}", event),
+        };
+
+        assert_eq!(event.guild_id, GuildId::from(41771983423143937));
+        assert_eq!(event.member.user.id, UserId::from(80351110224678912));
+        assert_eq!(event.member.nick.as_deref(), Some("NOT API SUPPORT"));
+    }
+
+    #[test]
+    fn test_message_update_partial() {
+        let data = json!({
+            "id": "334385199974967042",
+            "channel_id": "290926798999357250",
+            "embeds": []
+        });
+
+        let event = match Event::from_dispatch("MESSAGE_UPDATE", data).unwrap() {
+            Event::MessageUpdate(event) => event,
+            event => panic!("unexpected event: {:?}", event),
+        };
+
+        assert_eq!(event.id, MessageId::from(334385199974967042));
+        assert_eq!(event.channel_id, ChannelId::from(290926798999357250));
+        assert!(event.content.is_none());
+        assert_eq!(event.embeds.map(|e| e.len()), Some(0));
+    }
+
+    #[test]
+    fn test_message_reaction_add() {
+        let data = json!({
+            "user_id": "80351110224678912",
+            "channel_id": "290926798999357250",
+            "message_id": "334385199974967042",
+            "guild_id": "41771983423143937",
+            "emoji": { "id": null, "name": "🔥" }
+        });
+
+        let event = match Event::from_dispatch("MESSAGE_REACTION_ADD", data).unwrap() {
+            Event::MessageReactionAdd(event) => event,
+            event => panic!("unexpected event: {:?}", event),
+        };
+
+        assert_eq!(event.user_id, UserId::from(80351110224678912));
+        assert_eq!(event.emoji, PartialEmoji::standard("🔥"));
+    }
+
+    #[test]
+    fn test_unknown() {
+        let data = json!({ "foo": "bar" });
+
+        match Event::from_dispatch("SOME_NEW_EVENT", data.clone()).unwrap() {
+            Event::Unknown(event) => {
+                assert_eq!(event.kind, "SOME_NEW_EVENT");
+                assert_eq!(event.data, data);
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+}
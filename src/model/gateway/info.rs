@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// Information on connecting to the gateway.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GatewayInfo {
+    /// The WebSocket URL of the gateway.
+    pub url: String,
+}
+
+/// Information on connecting to the gateway as a bot user.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BotGatewayInfo {
+    #[serde(flatten)]
+    gateway: GatewayInfo,
+    /// The recommended number of shards to connect with.
+    pub shards: u64,
+    /// The limits on starting new sessions.
+    pub session_start_limit: SessionStartLimit,
+}
+wrap!(BotGatewayInfo => mut gateway: GatewayInfo);
+
+/// The limits on the number of sessions that can be started by a bot user.
+///
+/// Each identify with the gateway counts as a session start, resuming a
+/// session does not.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SessionStartLimit {
+    /// The total number of session starts allowed in the current period.
+    pub total: u64,
+    /// The number of session starts remaining in the current period.
+    pub remaining: u64,
+    /// The number of milliseconds until the limit resets.
+    pub reset_after: u64,
+}
+
+impl_eq_fields!(GatewayInfo: [url]);
+impl_eq_fields!(BotGatewayInfo: [gateway, shards, session_start_limit]);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_gateway() {
+        let value = json!({
+            "url": "wss://gateway.discord.gg"
+        });
+        let gateway = GatewayInfo {
+            url: "wss://gateway.discord.gg".to_owned(),
+        };
+
+        let deserialized = GatewayInfo::deserialize(&value).unwrap();
+        assert_eq_fields!(gateway, deserialized);
+    }
+
+    #[test]
+    fn test_deserialize_bot_gateway() {
+        let value = json!({
+            "url": "wss://gateway.discord.gg",
+            "shards": 9,
+            "session_start_limit": {
+                "total": 1000,
+                "remaining": 999,
+                "reset_after": 14400000
+            }
+        });
+        let gateway = BotGatewayInfo {
+            gateway: GatewayInfo {
+                url: "wss://gateway.discord.gg".to_owned(),
+            },
+            shards: 9,
+            session_start_limit: SessionStartLimit {
+                total: 1000,
+                remaining: 999,
+                reset_after: 14_400_000,
+            },
+        };
+
+        let deserialized = BotGatewayInfo::deserialize(&value).unwrap();
+        assert_eq_fields!(gateway, deserialized);
+    }
+
+    #[test]
+    fn test_serialize_bot_gateway() {
+        let value = json!({
+            "url": "wss://gateway.discord.gg",
+            "shards": 9,
+            "session_start_limit": {
+                "total": 1000,
+                "remaining": 999,
+                "reset_after": 14400000
+            }
+        });
+        let gateway = BotGatewayInfo {
+            gateway: GatewayInfo {
+                url: "wss://gateway.discord.gg".to_owned(),
+            },
+            shards: 9,
+            session_start_limit: SessionStartLimit {
+                total: 1000,
+                remaining: 999,
+                reset_after: 14_400_000,
+            },
+        };
+
+        assert_eq!(value, serde_json::to_value(&gateway).unwrap());
+    }
+}
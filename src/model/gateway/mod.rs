@@ -1,4 +1,9 @@
 //! Models related to the gateway.
 
+mod info;
+
 pub mod activity;
+pub mod event;
 pub mod presence;
+
+pub use self::info::{BotGatewayInfo, GatewayInfo, SessionStartLimit};
@@ -99,11 +99,18 @@ pub struct Presence {
     /// The user the presence relates to.
     pub user: PartialUser,
     /// The roles of the user.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub roles: Vec<RoleId>,
     /// The current activity of the user.
     pub game: Option<Activity>,
     /// The ID of the guild.
-    pub guild_id: GuildId,
+    ///
+    /// # Notes
+    ///
+    /// This is not sent for presences included in a `GUILD_CREATE` gateway
+    /// event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
     /// The online status of the user.
     pub status: OnlineStatus,
     /// The current activities of the user.
@@ -1,78 +1,598 @@
-/// The [type of action] that occurred in an [`AuditLogEntry`].
+use std::collections::HashMap;
+
+use serde::de;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::model::channel::permissions::{OverwriteId, PermissionOverwrite};
+use crate::model::color::Color;
+use crate::model::guild::{
+    ExplicitContentFilterLevel, MessageNotificationLevel, MfaLevel, PartialIntegration,
+    VerificationLevel,
+};
+use crate::model::id::{
+    ApplicationId, AuditLogEntryId, ChannelId, IntegrationId, MessageId, RoleId, UserId, WebhookId,
+};
+use crate::model::permissions::Permissions;
+use crate::model::snowflake::Snowflake;
+use crate::model::user::User;
+use crate::model::utils::{serde_id_map, serde_option_string_u64};
+use crate::model::voice::VoiceRegionId;
+use crate::model::webhook::Webhook;
+
+int_enum! {
+    /// The [type of action] that occurred in an [`AuditLogEntry`].
+    ///
+    /// [type of action]: https://discordapp.com/developers/docs/resources/audit-log#audit-log-entry-object-audit-log-events
+    /// [`AuditLogEntry`]: struct.AuditLogEntry.html
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum AuditLogEvent: u8 {
+        /// The guild settings were updated.
+        GuildUpdate = 1,
+        /// A channel was created.
+        ChannelCreate = 10,
+        /// A channel was updated.
+        ChannelUpdate = 11,
+        /// A channel was deleted.
+        ChannelDelete = 12,
+        /// A channel permission overwrite was created.
+        ChannelOverwriteCreate = 13,
+        /// A channel permission overwrite was updated.
+        ChannelOverwriteUpdate = 14,
+        /// A channel permission overwrite was deleted.
+        ChannelOverwriteDelete = 15,
+        /// A member was kicked.
+        MemberKick = 20,
+        /// A member prune was executed.
+        MemberPrune = 21,
+        /// A member was banned.
+        MemberBanAdd = 22,
+        /// A member was unbanned.
+        MemberBanRemove = 23,
+        /// A member was updated.
+        MemberUpdate = 24,
+        /// A member's roles were updated.
+        MemberRoleUpdate = 25,
+        /// A member was moved to a voice channel.
+        MemberMove = 26,
+        /// A member was disconnected from a voice channel.
+        MemberDisconnect = 27,
+        /// A bot was added to the guild.
+        BotAdd = 28,
+        /// A role was created.
+        RoleCreate = 30,
+        /// A role was updated.
+        RoleUpdate = 31,
+        /// A role was deleted.
+        RoleDelete = 32,
+        /// An invite was created.
+        InviteCreate = 40,
+        /// An invite was updated.
+        InviteUpdate = 41,
+        /// An invite was deleted.
+        InviteDelete = 42,
+        /// A webhook was created.
+        WebhookCreate = 50,
+        /// A webhook was updated.
+        WebhookUpdate = 51,
+        /// A webhook was deleted.
+        WebhookDelete = 52,
+        /// An emoji was created.
+        EmojiCreate = 60,
+        /// An emoji was updated.
+        EmojiUpdate = 61,
+        /// An emoji was deleted.
+        EmojiDelete = 62,
+        /// A message was deleted.
+        MessageDelete = 72,
+        /// Messages were bulk deleted.
+        MessageBulkDelete = 73,
+        /// A message was pinned.
+        MessagePin = 74,
+        /// A message was unpinned.
+        MessageUnpin = 75,
+        /// An integration was created.
+        IntegrationCreate = 80,
+        /// An integration was updated.
+        IntegrationUpdate = 81,
+        /// An integration was deleted.
+        IntegrationDelete = 82,
+    }
+}
+
+/// The audit log of a [`Guild`].
+///
+/// [`Guild`]: struct.Guild.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuditLog {
+    /// The webhooks found in the audit log.
+    #[serde(with = "serde_id_map")]
+    pub webhooks: HashMap<WebhookId, Webhook>,
+    /// The users found in the audit log.
+    #[serde(with = "serde_id_map")]
+    pub users: HashMap<UserId, User>,
+    /// The entries in the audit log, most recent first.
+    #[serde(rename = "audit_log_entries")]
+    pub entries: Vec<AuditLogEntry>,
+    /// The partial integrations found in the audit log.
+    #[serde(default, with = "serde_id_map")]
+    pub integrations: HashMap<IntegrationId, PartialIntegration>,
+}
+
+impl_eq_fields!(AuditLog: (a, b) => {
+    assert_eq_fields!(map => a.webhooks, b.webhooks);
+    assert_eq_fields!(map => a.users, b.users);
+    assert_eq_fields!(a.entries, b.entries);
+    assert_eq_fields!(map => a.integrations, b.integrations);
+});
+
+/// An entry in an [`AuditLog`].
+///
+/// [`AuditLog`]: struct.AuditLog.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuditLogEntry {
+    /// The ID of the entry.
+    pub id: AuditLogEntryId,
+    /// The ID of the affected entity (webhook, user, role, etc.).
+    pub target_id: Option<String>,
+    /// The changes made to the target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<AuditLogChange>,
+    /// The ID of the user who made the changes.
+    pub user_id: UserId,
+    /// The type of action that occurred.
+    pub action_type: AuditLogEvent,
+    /// Additional info for certain action types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<AuditLogOptions>,
+    /// The reason for the change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl_eq_fields!(AuditLogEntry: [
+    id,
+    target_id,
+    changes,
+    user_id,
+    action_type,
+    options,
+    reason,
+]);
+
+/// Additional info included in an [`AuditLogEntry`] for certain action types.
+///
+/// [`AuditLogEntry`]: struct.AuditLogEntry.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AuditLogOptions {
+    /// The number of days after which inactive members were kicked.
+    ///
+    /// Included for [`MemberPrune`] actions.
+    ///
+    /// [`MemberPrune`]: enum.AuditLogEvent.html#variant.MemberPrune
+    #[serde(default, with = "serde_option_string_u64")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_member_days: Option<u64>,
+    /// The number of members removed by the prune.
+    ///
+    /// Included for [`MemberPrune`] actions.
+    ///
+    /// [`MemberPrune`]: enum.AuditLogEvent.html#variant.MemberPrune
+    #[serde(default, with = "serde_option_string_u64")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members_removed: Option<u64>,
+    /// The channel in which the entities were targeted.
+    ///
+    /// Included for [`MemberMove`], [`MessageDelete`], [`MessagePin`] and
+    /// [`MessageUnpin`] actions.
+    ///
+    /// [`MemberMove`]: enum.AuditLogEvent.html#variant.MemberMove
+    /// [`MessageDelete`]: enum.AuditLogEvent.html#variant.MessageDelete
+    /// [`MessagePin`]: enum.AuditLogEvent.html#variant.MessagePin
+    /// [`MessageUnpin`]: enum.AuditLogEvent.html#variant.MessageUnpin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// The ID of the message that was targeted.
+    ///
+    /// Included for [`MessagePin`] and [`MessageUnpin`] actions.
+    ///
+    /// [`MessagePin`]: enum.AuditLogEvent.html#variant.MessagePin
+    /// [`MessageUnpin`]: enum.AuditLogEvent.html#variant.MessageUnpin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<MessageId>,
+    /// The number of entities that were targeted.
+    ///
+    /// Included for [`MemberMove`], [`MemberDisconnect`], [`MessageDelete`]
+    /// and [`MessageBulkDelete`] actions.
+    ///
+    /// [`MemberMove`]: enum.AuditLogEvent.html#variant.MemberMove
+    /// [`MemberDisconnect`]: enum.AuditLogEvent.html#variant.MemberDisconnect
+    /// [`MessageDelete`]: enum.AuditLogEvent.html#variant.MessageDelete
+    /// [`MessageBulkDelete`]: enum.AuditLogEvent.html#variant.MessageBulkDelete
+    #[serde(default, with = "serde_option_string_u64")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    /// The role or user of the overwritten permissions.
+    ///
+    /// Included for channel permission overwrite actions.
+    #[serde(flatten)]
+    pub overwrite: Option<OverwriteId>,
+    /// The name of the role of the overwritten permissions.
+    ///
+    /// Included for channel permission overwrite actions on roles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_name: Option<String>,
+}
+
+/// A role added to or removed from a member in an [`AuditLogChange`].
 ///
-/// [type of action]: https://discordapp.com/developers/docs/resources/audit-log#audit-log-entry-object-audit-log-events
+/// [`AuditLogChange`]: enum.AuditLogChange.html
 #[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub enum AuditLogEvent {
-    /// The guild settings were updated.
-    GuildUpdate = 1,
-    /// A channel was created.
-    ChannelCreate = 10,
-    /// A channel was updated.
-    ChannelUpdate = 11,
-    /// A channel was deleted.
-    ChannelDelete = 12,
-    /// A channel permission overwrite was created.
-    ChannelOverwriteCreate = 13,
-    /// A channel permission overwrite was updated.
-    ChannelOverwriteUpdate = 14,
-    /// A channel permission overwrite was deleted.
-    ChannelOverwriteDelete = 15,
-    /// A member was kicked.
-    MemberKick = 20,
-    /// A member prune was executed.
-    MemberPrune = 21,
-    /// A member was banned.
-    MemberBanAdd = 22,
-    /// A member was unbanned.
-    MemberBanRemove = 23,
-    /// A member was updated.
-    MemberUpdate = 24,
-    /// A member's roles were updated.
-    MemberRoleUpdate = 25,
-    /// A member was moved to a voice channel.
-    MemberMove = 26,
-    /// A member was disconnected from a voice channel.
-    MemberDisconnect = 27,
-    /// A bot was added to the guild.
-    BotAdd = 28,
-    /// A role was created.
-    RoleCreate = 30,
-    /// A role was updated.
-    RoleUpdate = 31,
-    /// A role was deleted.
-    RoleDelete = 32,
-    /// An invite was created.
-    InviteCreate = 40,
-    /// An invite was updated.
-    InviteUpdate = 41,
-    /// An invite was deleted.
-    InviteDelete = 42,
-    /// A webhook was created.
-    WebhookCreate = 50,
-    /// A webhook was updated.
-    WebhookUpdate = 51,
-    /// A webhook was deleted.
-    WebhookDelete = 52,
-    /// An emoji was created.
-    EmojiCreate = 60,
-    /// An emoji was updated.
-    EmojiUpdate = 61,
-    /// An emoji was deleted.
-    EmojiDelete = 62,
-    /// A message was deleted.
-    MessageDelete = 72,
-    /// Messages were bulk deleted.
-    MessageBulkDelete = 73,
-    /// A message was pinned.
-    MessagePin = 74,
-    /// A message was unpinned.
-    MessageUnpin = 75,
-    /// An integration was created.
-    IntegrationCreate = 80,
-    /// An integration was updated.
-    IntegrationUpdate = 81,
-    /// An integration was deleted.
-    IntegrationDelete = 82,
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AuditLogRole {
+    /// The ID of the role.
+    pub id: RoleId,
+    /// The name of the role.
+    pub name: String,
+}
+
+/// The old and new values of a key changed in an [`AuditLogEntry`].
+///
+/// [`AuditLogEntry`]: struct.AuditLogEntry.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Change<T> {
+    /// The value of the key before the change.
+    pub old: Option<T>,
+    /// The value of the key after the change.
+    pub new: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct RawChange {
+    key: String,
+    #[serde(default)]
+    old_value: Option<Value>,
+    #[serde(default)]
+    new_value: Option<Value>,
+}
+
+fn from_raw<T, E>(value: Option<Value>) -> Result<Option<T>, E>
+where
+    T: de::DeserializeOwned,
+    E: de::Error,
+{
+    match value {
+        Some(value) => T::deserialize(value).map(Some).map_err(E::custom),
+        None => Ok(None),
+    }
+}
+
+fn serialize_raw<S, T>(
+    serializer: S,
+    key: &str,
+    old: &Option<T>,
+    new: &Option<T>,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let len = 1 + old.is_some() as usize + new.is_some() as usize;
+    let mut state = serializer.serialize_struct("AuditLogChange", len)?;
+    state.serialize_field("key", key)?;
+    match old {
+        Some(old) => state.serialize_field("old_value", old)?,
+        None => state.skip_field("old_value")?,
+    }
+    match new {
+        Some(new) => state.serialize_field("new_value", new)?,
+        None => state.skip_field("new_value")?,
+    }
+    state.end()
+}
+
+macro_rules! audit_log_change {
+    ($(
+        $(#[$attr:meta])*
+        $key:literal => $Variant:ident($T:ty),
+    )*) => {
+        /// A change made to an entity in an [`AuditLogEntry`], keyed by the
+        /// [change key].
+        ///
+        /// [`AuditLogEntry`]: struct.AuditLogEntry.html
+        /// [change key]: https://discordapp.com/developers/docs/resources/audit-log#audit-log-change-object-audit-log-change-key
+        #[non_exhaustive]
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum AuditLogChange {
+            $(
+                $(#[$attr])*
+                $Variant(Change<$T>),
+            )*
+            /// A change to a key that is not known to the library.
+            Unknown {
+                /// The name of the changed key.
+                key: String,
+                /// The value of the key before the change.
+                old: Option<Value>,
+                /// The value of the key after the change.
+                new: Option<Value>,
+            },
+        }
+
+        impl AuditLogChange {
+            /// The name of the changed key.
+            pub fn key(&self) -> &str {
+                match self {
+                    $(AuditLogChange::$Variant(_) => $key,)*
+                    AuditLogChange::Unknown { key, .. } => key,
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for AuditLogChange {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let raw = RawChange::deserialize(deserializer)?;
+                let change = match raw.key.as_str() {
+                    $($key => AuditLogChange::$Variant(Change {
+                        old: from_raw(raw.old_value)?,
+                        new: from_raw(raw.new_value)?,
+                    }),)*
+                    _ => AuditLogChange::Unknown {
+                        key: raw.key,
+                        old: raw.old_value,
+                        new: raw.new_value,
+                    },
+                };
+                Ok(change)
+            }
+        }
+
+        impl Serialize for AuditLogChange {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                match self {
+                    $(AuditLogChange::$Variant(change) => {
+                        serialize_raw(serializer, $key, &change.old, &change.new)
+                    })*
+                    AuditLogChange::Unknown { key, old, new } => {
+                        serialize_raw(serializer, key, old, new)
+                    }
+                }
+            }
+        }
+    };
+}
+
+audit_log_change! {
+    /// The name of a guild, channel or role changed.
+    "name" => Name(String),
+    /// The icon of a guild changed.
+    "icon_hash" => IconHash(String),
+    /// The invite splash of a guild changed.
+    "splash_hash" => SplashHash(String),
+    /// The owner of a guild changed.
+    "owner_id" => OwnerId(UserId),
+    /// The voice region of a guild changed.
+    "region" => Region(VoiceRegionId),
+    /// The AFK channel of a guild changed.
+    "afk_channel_id" => AfkChannelId(ChannelId),
+    /// The AFK timeout of a guild changed.
+    "afk_timeout" => AfkTimeout(u64),
+    /// The two-factor authentication requirement of a guild changed.
+    "mfa_level" => MfaLevel(MfaLevel),
+    /// The verification level of a guild changed.
+    "verification_level" => VerificationLevel(VerificationLevel),
+    /// The explicit content filter of a guild changed.
+    "explicit_content_filter" => ExplicitContentFilter(ExplicitContentFilterLevel),
+    /// The default message notification level of a guild changed.
+    "default_message_notifications" => DefaultMessageNotifications(MessageNotificationLevel),
+    /// The vanity URL code of a guild changed.
+    "vanity_url_code" => VanityUrlCode(String),
+    /// Roles were added to a member.
+    "$add" => AddRoles(Vec<AuditLogRole>),
+    /// Roles were removed from a member.
+    "$remove" => RemoveRoles(Vec<AuditLogRole>),
+    /// The number of days after which inactive members are kicked changed.
+    "prune_delete_days" => PruneDeleteDays(u64),
+    /// Whether the widget of a guild is enabled changed.
+    "widget_enabled" => WidgetEnabled(bool),
+    /// The widget channel of a guild changed.
+    "widget_channel_id" => WidgetChannelId(ChannelId),
+    /// The position of a channel or role changed.
+    "position" => Position(u64),
+    /// The topic of a text channel changed.
+    "topic" => Topic(String),
+    /// The bitrate of a voice channel changed.
+    "bitrate" => Bitrate(u64),
+    /// The permission overwrites of a channel changed.
+    "permission_overwrites" => PermissionOverwrites(Vec<PermissionOverwrite>),
+    /// Whether a channel is NSFW changed.
+    "nsfw" => Nsfw(bool),
+    /// The application ID of a webhook or bot changed.
+    "application_id" => ApplicationId(ApplicationId),
+    /// The slowmode rate limit of a text channel changed.
+    "rate_limit_per_user" => RateLimitPerUser(u64),
+    /// The permissions of a role changed.
+    "permissions" => Permissions(Permissions),
+    /// The color of a role changed.
+    "color" => Color(Color),
+    /// Whether a role is displayed separately changed.
+    "hoist" => Hoist(bool),
+    /// Whether a role is mentionable changed.
+    "mentionable" => Mentionable(bool),
+    /// The allowed permissions of a permission overwrite changed.
+    "allow" => Allow(Permissions),
+    /// The denied permissions of a permission overwrite changed.
+    "deny" => Deny(Permissions),
+    /// The code of an invite changed.
+    "code" => Code(String),
+    /// The channel of an invite changed.
+    "channel_id" => ChannelId(ChannelId),
+    /// The creator of an invite changed.
+    "inviter_id" => InviterId(UserId),
+    /// The maximum number of uses of an invite changed.
+    "max_uses" => MaxUses(u64),
+    /// The number of times an invite has been used changed.
+    "uses" => Uses(u64),
+    /// How long an invite is valid for changed.
+    "max_age" => MaxAge(u64),
+    /// Whether an invite grants temporary membership changed.
+    "temporary" => Temporary(bool),
+    /// Whether a member is server deafened changed.
+    "deaf" => Deaf(bool),
+    /// Whether a member is server muted changed.
+    "mute" => Mute(bool),
+    /// The nickname of a member changed.
+    "nick" => Nick(String),
+    /// The avatar of a user changed.
+    "avatar_hash" => AvatarHash(String),
+    /// The ID of the changed entity.
+    "id" => Id(Snowflake),
+    /// The type of a channel or permission overwrite was changed.
+    ///
+    /// The type of a channel is an integer, while the type of a permission
+    /// overwrite is a string, so the raw value is retained.
+    "type" => Type(Value),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_audit_log() {
+        let value = json!({
+          "webhooks": [],
+          "users": [
+            {
+              "id": "80351110224678912",
+              "username": "Nelly",
+              "discriminator": "1337",
+              "avatar": null
+            }
+          ],
+          "audit_log_entries": [
+            {
+              "id": "672465524296908810",
+              "user_id": "80351110224678912",
+              "target_id": "290926798629997250",
+              "action_type": 1,
+              "changes": [
+                {"key": "name", "old_value": "Old", "new_value": "New"},
+                {"key": "afk_timeout", "old_value": 300, "new_value": 900}
+              ],
+              "reason": "Tidy up"
+            },
+            {
+              "id": "672465524296908811",
+              "user_id": "80351110224678912",
+              "target_id": "80351110224678913",
+              "action_type": 21,
+              "options": {
+                "delete_member_days": "7",
+                "members_removed": "2"
+              }
+            }
+          ],
+          "integrations": []
+        });
+
+        let audit_log = AuditLog::deserialize(&value).unwrap();
+        assert_eq!(audit_log.users.len(), 1);
+        assert!(audit_log
+            .users
+            .contains_key(&UserId::from(80351110224678912)));
+        assert_eq!(audit_log.entries.len(), 2);
+
+        let entry = &audit_log.entries[0];
+        assert_eq!(entry.id, AuditLogEntryId::from(672465524296908810));
+        assert_eq!(entry.action_type, AuditLogEvent::GuildUpdate);
+        assert_eq!(entry.reason.as_deref(), Some("Tidy up"));
+        assert_eq!(
+            entry.changes,
+            vec![
+                AuditLogChange::Name(Change {
+                    old: Some("Old".to_owned()),
+                    new: Some("New".to_owned()),
+                }),
+                AuditLogChange::AfkTimeout(Change {
+                    old: Some(300),
+                    new: Some(900),
+                }),
+            ]
+        );
+
+        let entry = &audit_log.entries[1];
+        assert_eq!(entry.action_type, AuditLogEvent::MemberPrune);
+        assert_eq!(
+            entry.options,
+            Some(AuditLogOptions {
+                delete_member_days: Some(7),
+                members_removed: Some(2),
+                ..AuditLogOptions::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_deserialize_overwrite_options() {
+        let value = json!({
+          "id": "80351110224678912",
+          "type": "role",
+          "role_name": "Moderator"
+        });
+        let options = AuditLogOptions {
+            overwrite: Some(OverwriteId::Role(RoleId::from(80351110224678912))),
+            role_name: Some("Moderator".to_owned()),
+            ..AuditLogOptions::default()
+        };
+
+        assert_eq!(options, AuditLogOptions::deserialize(&value).unwrap());
+        assert_eq!(value, serde_json::to_value(&options).unwrap());
+    }
+
+    #[test]
+    fn test_change_roles() {
+        let value = json!({
+          "key": "$add",
+          "new_value": [{"id": "80351110224678912", "name": "Moderator"}]
+        });
+        let change = AuditLogChange::AddRoles(Change {
+            old: None,
+            new: Some(vec![AuditLogRole {
+                id: RoleId::from(80351110224678912),
+                name: "Moderator".to_owned(),
+            }]),
+        });
+
+        assert_eq!(change, AuditLogChange::deserialize(&value).unwrap());
+        assert_eq!(value, serde_json::to_value(&change).unwrap());
+        assert_eq!(change.key(), "$add");
+    }
+
+    #[test]
+    fn test_change_unknown() {
+        let value = json!({"key": "new_key", "old_value": [1, 2], "new_value": 3});
+        let change = AuditLogChange::Unknown {
+            key: "new_key".to_owned(),
+            old: Some(json!([1, 2])),
+            new: Some(json!(3)),
+        };
+
+        assert_eq!(change, AuditLogChange::deserialize(&value).unwrap());
+        assert_eq!(value, serde_json::to_value(&change).unwrap());
+    }
 }
@@ -8,7 +8,7 @@ use crate::model::id::RoleId;
 use crate::model::user::User;
 use crate::model::utils::default_true;
 
-pub use self::partial::{CustomEmoji, PartialEmoji};
+pub use self::partial::{CustomEmoji, EmojiParseError, PartialEmoji};
 
 /// A guild emoji.
 #[non_exhaustive]
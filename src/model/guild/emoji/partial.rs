@@ -1,13 +1,21 @@
 use std::borrow::{Borrow, Cow};
 use std::fmt::{self, Display};
 use std::hash::{Hash, Hasher};
+use std::num::ParseIntError;
 use std::str::FromStr;
 
 use serde::de;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
 
 use crate::model::id::EmojiId;
 
+/// The name substituted for custom emojis with no name when they are rendered.
+///
+/// Rendering a custom emoji doesn't require knowing the name, however if no
+/// name is provided the emoji will not render.
+const UNKNOWN_NAME: &str = "_";
+
 /// A custom guild emoji with partial information.
 #[non_exhaustive]
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -58,12 +66,6 @@ impl Hash for CustomEmoji {
 
 impl Display for CustomEmoji {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        /// Rendering a custom emoji doesn't require knowing the name, however
-        /// if no name is provided the emoji will not render.
-        ///
-        /// Work around this issue by substituting unknown names with `_`.
-        const UNKNOWN_NAME: &str = "_";
-
         if self.animated {
             f.write_str("<a:")?;
         } else {
@@ -163,14 +165,159 @@ impl From<&'static str> for PartialEmoji {
     }
 }
 
+/// An error parsing a [`PartialEmoji`].
+///
+/// [`PartialEmoji`]: enum.PartialEmoji.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Error)]
+pub enum EmojiParseError {
+    /// The input was empty.
+    #[error("empty emoji")]
+    Empty,
+    /// The input was not a valid custom emoji in the form `<:name:id>` or
+    /// `<a:name:id>`.
+    #[error("invalid custom emoji: {0}")]
+    InvalidCustom(String),
+    /// An error parsing the ID of a custom emoji.
+    #[error("invalid custom emoji ID: {0}")]
+    InvalidId(#[source] ParseIntError),
+    /// The input was not a standard unicode emoji sequence.
+    #[error("invalid standard emoji: {0}")]
+    InvalidStandard(String),
+}
+
 impl FromStr for PartialEmoji {
-    type Err = std::convert::Infallible;
+    type Err = EmojiParseError;
 
+    /// Parses an emoji from either a custom emoji in the chat syntax
+    /// (`<:name:id>` or `<a:name:id>`), or a standard unicode emoji sequence.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(PartialEmoji::standard(s.to_owned()))
+        if s.is_empty() {
+            return Err(EmojiParseError::Empty);
+        }
+
+        if s.starts_with('<') {
+            return parse_custom(s).map(PartialEmoji::Custom);
+        }
+
+        if is_emoji_sequence(s) {
+            Ok(PartialEmoji::standard(s.to_owned()))
+        } else {
+            Err(EmojiParseError::InvalidStandard(s.to_owned()))
+        }
     }
 }
 
+/// Parses a custom emoji from the chat syntax, as rendered by the `Display`
+/// implementation of [`CustomEmoji`].
+///
+/// The `_` placeholder rendered for emojis with no name is parsed back to no
+/// name.
+///
+/// [`CustomEmoji`]: struct.CustomEmoji.html
+fn parse_custom(s: &str) -> Result<CustomEmoji, EmojiParseError> {
+    let invalid = || EmojiParseError::InvalidCustom(s.to_owned());
+
+    if s.len() < 2 || !s.starts_with('<') || !s.ends_with('>') {
+        return Err(invalid());
+    }
+    let inner = &s[1..s.len() - 1];
+
+    let (animated, inner) = if inner.starts_with("a:") {
+        (true, &inner[2..])
+    } else if inner.starts_with(':') {
+        (false, &inner[1..])
+    } else {
+        return Err(invalid());
+    };
+
+    let mut parts = inner.splitn(2, ':');
+    let name = parts.next().ok_or_else(invalid)?;
+    let id = parts.next().ok_or_else(invalid)?;
+
+    let name = if name == UNKNOWN_NAME {
+        None
+    } else {
+        let valid_name = (2..=32).contains(&name.len())
+            && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+        if !valid_name {
+            return Err(invalid());
+        }
+        Some(Cow::from(name.to_owned()))
+    };
+
+    let id: u64 = id.parse().map_err(EmojiParseError::InvalidId)?;
+    Ok(CustomEmoji {
+        id: EmojiId::from(id),
+        name,
+        animated,
+    })
+}
+
+/// Checks whether a string is a sequence of unicode emoji characters.
+///
+/// This is an approximation of the unicode emoji specification, accepting any
+/// combination of emoji characters, modifiers, zero width joiners, variation
+/// selectors, keycaps and tags, provided at least one emoji character is
+/// present.
+fn is_emoji_sequence(s: &str) -> bool {
+    /// Characters that may be part of an emoji sequence, but cannot form an
+    /// emoji on their own.
+    fn is_component(ch: char) -> bool {
+        match ch {
+            // Zero width joiner.
+            '\u{200D}'
+            // Variation selectors.
+            | '\u{FE0E}' | '\u{FE0F}'
+            // Combining enclosing keycap.
+            | '\u{20E3}'
+            // Tags, used for subdivision flags.
+            | '\u{E0020}'..='\u{E007F}' => true,
+            _ => false,
+        }
+    }
+
+    fn is_emoji(ch: char) -> bool {
+        match ch {
+            '\u{00A9}' | '\u{00AE}' | '\u{203C}' | '\u{2049}' | '\u{2122}' | '\u{2139}'
+            | '\u{2194}'..='\u{21AA}'
+            | '\u{231A}'..='\u{23FF}'
+            | '\u{24C2}'
+            | '\u{25AA}'..='\u{25FE}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2934}' | '\u{2935}'
+            | '\u{2B05}'..='\u{2B55}'
+            | '\u{3030}' | '\u{303D}' | '\u{3297}' | '\u{3299}'
+            // Pictographs, emoticons, symbols, regional indicators and skin
+            // tone modifiers.
+            | '\u{1F000}'..='\u{1FAFF}' => true,
+            _ => false,
+        }
+    }
+
+    let mut has_emoji = false;
+    let mut chars = s.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if is_emoji(ch) {
+            has_emoji = true;
+        } else if ch.is_ascii_digit() || ch == '#' || ch == '*' {
+            // Keycap sequences, such as `#️⃣`.
+            if chars.peek() == Some(&'\u{FE0F}') {
+                chars.next();
+            }
+            if chars.next() != Some('\u{20E3}') {
+                return false;
+            }
+            has_emoji = true;
+        } else if !is_component(ch) {
+            return false;
+        }
+    }
+
+    has_emoji
+}
+
 impl Serialize for PartialEmoji {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
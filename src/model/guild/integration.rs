@@ -0,0 +1,154 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::model::id::{IntegrationId, RoleId};
+use crate::model::user::User;
+
+int_enum! {
+    /// The behavior of an [`Integration`] when a subscription expires.
+    ///
+    /// [`Integration`]: struct.Integration.html
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum IntegrationExpireBehavior: u8 {
+        /// The integration role is removed from the member.
+        RemoveRole = 0,
+        /// The member is kicked from the guild.
+        Kick = 1,
+    }
+}
+
+/// The account of an [`Integration`].
+///
+/// [`Integration`]: struct.Integration.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IntegrationAccount {
+    /// The ID of the account.
+    pub id: String,
+    /// The name of the account.
+    pub name: String,
+}
+
+/// A guild integration, such as Twitch or YouTube.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Integration {
+    /// The ID of the integration.
+    pub id: IntegrationId,
+    /// The name of the integration.
+    pub name: String,
+    /// The type of the integration (eg. `twitch`, `youtube`).
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Whether the integration is enabled.
+    pub enabled: bool,
+    /// Whether the integration is syncing.
+    pub syncing: bool,
+    /// The ID of the role the integration uses for subscribers.
+    pub role_id: RoleId,
+    /// The behavior of the integration when a subscription expires.
+    pub expire_behavior: IntegrationExpireBehavior,
+    /// The grace period in days before expiring subscribers.
+    pub expire_grace_period: u64,
+    /// The user of the integration.
+    pub user: User,
+    /// The account of the integration.
+    pub account: IntegrationAccount,
+    /// When the integration was last synced.
+    pub synced_at: DateTime<FixedOffset>,
+}
+
+/// A partial [`Integration`], as included in an audit log.
+///
+/// [`Integration`]: struct.Integration.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PartialIntegration {
+    /// The ID of the integration.
+    pub id: IntegrationId,
+    /// The name of the integration.
+    pub name: String,
+    /// The type of the integration (eg. `twitch`, `youtube`).
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The account of the integration.
+    pub account: IntegrationAccount,
+}
+
+impl_eq_fields!(IntegrationAccount: [id, name]);
+impl_eq_fields!(PartialIntegration: [id, name, kind, account]);
+impl_eq_fields!(Integration: [
+    id,
+    name,
+    kind,
+    enabled,
+    syncing,
+    role_id,
+    expire_behavior,
+    expire_grace_period,
+    user,
+    account,
+    synced_at,
+]);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::model::id::UserId;
+    use crate::model::user::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let value = json!({
+          "id": "33590653072239123",
+          "name": "A Name",
+          "type": "twitch",
+          "enabled": true,
+          "syncing": false,
+          "role_id": "41771983423143936",
+          "expire_behavior": 0,
+          "expire_grace_period": 1,
+          "user": {
+            "id": "80351110224678912",
+            "username": "Nelly",
+            "discriminator": "1337",
+            "avatar": "8342729096ea3675442027381ff50dfe"
+          },
+          "account": {
+            "id": "12345",
+            "name": "A Name"
+          },
+          "synced_at": "2015-04-26T06:26:56.936000+00:00"
+        });
+        let integration = Integration {
+            id: IntegrationId::from(33590653072239123),
+            name: "A Name".to_owned(),
+            kind: "twitch".to_owned(),
+            enabled: true,
+            syncing: false,
+            role_id: RoleId::from(41771983423143936),
+            expire_behavior: IntegrationExpireBehavior::RemoveRole,
+            expire_grace_period: 1,
+            user: User {
+                id: UserId::from(80351110224678912),
+                name: "Nelly".to_owned(),
+                discriminator: Discriminator::new(1337).unwrap(),
+                avatar: Some("8342729096ea3675442027381ff50dfe".to_owned()),
+                bot: false,
+                system: false,
+            },
+            account: IntegrationAccount {
+                id: "12345".to_owned(),
+                name: "A Name".to_owned(),
+            },
+            synced_at: DateTime::parse_from_rfc3339("2015-04-26T06:26:56.936000+00:00").unwrap(),
+        };
+
+        let deserialized = Integration::deserialize(&value).unwrap();
+        assert_eq_fields!(integration, deserialized);
+    }
+}
@@ -2,7 +2,9 @@
 
 mod audit_log;
 mod emoji;
+mod integration;
 mod member;
+mod permissions;
 mod role;
 
 use std::collections::{HashMap, HashSet};
@@ -16,32 +18,39 @@ use crate::model::gateway::presence::Presence;
 use crate::model::id::{ApplicationId, ChannelId, EmojiId, GuildId, RoleId, UserId};
 use crate::model::misc::Locale;
 use crate::model::permissions::Permissions;
+use crate::model::user::User;
 use crate::model::utils::{is_false, serde_id_map};
 use crate::model::voice::{VoiceRegionId, VoiceState};
 
-pub use self::audit_log::AuditLogEvent;
-pub use self::emoji::{CustomEmoji, Emoji, PartialEmoji};
+pub use self::audit_log::{
+    AuditLog, AuditLogChange, AuditLogEntry, AuditLogEvent, AuditLogOptions, AuditLogRole, Change,
+};
+pub use self::emoji::{CustomEmoji, Emoji, EmojiParseError, PartialEmoji};
+pub use self::integration::{
+    Integration, IntegrationAccount, IntegrationExpireBehavior, PartialIntegration,
+};
 pub use self::member::{Member, PartialMember};
 pub use self::role::Role;
 
-/// The required level of criteria a user must meet, prior to being able to send
-/// messages in a [`Guild`].
-///
-/// [`Guild`]: struct.Guild.html
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum VerificationLevel {
-    /// Does not require any verification.
-    None = 0,
-    /// Must have a verified email on the user's Discord account.
-    Low = 1,
-    /// Must also be a registered user on Discord for longer than 5 minutes.
-    Medium = 2,
-    /// Must also be a member of the guild for longer than 10 minutes.
-    High = 3,
-    /// Must have a verified phone on the user's Discord account.
-    Higher = 4,
+int_enum! {
+    /// The required level of criteria a user must meet, prior to being able to
+    /// send messages in a [`Guild`].
+    ///
+    /// [`Guild`]: struct.Guild.html
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum VerificationLevel: u8 {
+        /// Does not require any verification.
+        None = 0,
+        /// Must have a verified email on the user's Discord account.
+        Low = 1,
+        /// Must also be a registered user on Discord for longer than 5 minutes.
+        Medium = 2,
+        /// Must also be a member of the guild for longer than 10 minutes.
+        High = 3,
+        /// Must have a verified phone on the user's Discord account.
+        Higher = 4,
+    }
 }
 
 impl Default for VerificationLevel {
@@ -50,16 +59,17 @@ impl Default for VerificationLevel {
     }
 }
 
-/// The default level of message notifications in a guild.
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum MessageNotificationLevel {
-    /// All messages will send notifications.
-    AllMessages = 0,
-    /// Only messages that mention a user or a user's role will send
-    /// notifications.
-    OnlyMentions = 1,
+int_enum! {
+    /// The default level of message notifications in a guild.
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum MessageNotificationLevel: u8 {
+        /// All messages will send notifications.
+        AllMessages = 0,
+        /// Only messages that mention a user or a user's role will send
+        /// notifications.
+        OnlyMentions = 1,
+    }
 }
 
 impl Default for MessageNotificationLevel {
@@ -68,18 +78,20 @@ impl Default for MessageNotificationLevel {
     }
 }
 
-/// The level of filter to apply to users that send messages containing explicit
-/// content.
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum ExplicitContentFilterLevel {
-    /// No filter will be applied.
-    Disabled = 0,
-    /// Only members with roles will be able to send explicit content.
-    MembersWithoutRoles = 1,
-    /// All members will have explicit content filtered from messages they send.
-    AllMembers = 2,
+int_enum! {
+    /// The level of filter to apply to users that send messages containing
+    /// explicit content.
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum ExplicitContentFilterLevel: u8 {
+        /// No filter will be applied.
+        Disabled = 0,
+        /// Only members with roles will be able to send explicit content.
+        MembersWithoutRoles = 1,
+        /// All members will have explicit content filtered from messages they
+        /// send.
+        AllMembers = 2,
+    }
 }
 
 impl Default for ExplicitContentFilterLevel {
@@ -90,8 +102,8 @@ impl Default for ExplicitContentFilterLevel {
 
 /// A feature enabled for a guild.
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
 pub enum GuildFeature {
     /// The guild has access to set an invite splash background.
     InviteSplash,
@@ -118,18 +130,71 @@ pub enum GuildFeature {
     AnimatedIcon,
     /// The guild has access to set a guild banner image.
     Banner,
+    /// A feature that is not known to the library.
+    Unknown(String),
 }
 
-/// The required level of multi-factor authentication required for a guild.
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum MfaLevel {
-    /// Multi-factor authentication is not required.
-    None = 0,
-    /// Multi-factor authentication is required for members to take moderation
-    /// actions (eg. kick, ban, delete messages).
-    Elevated = 1,
+impl GuildFeature {
+    /// Returns the name of the feature used by Discord.
+    pub fn as_str(&self) -> &str {
+        match self {
+            GuildFeature::InviteSplash => "INVITE_SPLASH",
+            GuildFeature::VipRegions => "VIP_REGIONS",
+            GuildFeature::VanityUrl => "VANITY_URL",
+            GuildFeature::Verified => "VERIFIED",
+            GuildFeature::Partnered => "PARTNERED",
+            GuildFeature::Public => "PUBLIC",
+            GuildFeature::Commerce => "COMMERCE",
+            GuildFeature::News => "NEWS",
+            GuildFeature::Discoverable => "DISCOVERABLE",
+            GuildFeature::Featurable => "FEATURABLE",
+            GuildFeature::AnimatedIcon => "ANIMATED_ICON",
+            GuildFeature::Banner => "BANNER",
+            GuildFeature::Unknown(feature) => feature,
+        }
+    }
+}
+
+impl From<String> for GuildFeature {
+    fn from(feature: String) -> Self {
+        match feature.as_str() {
+            "INVITE_SPLASH" => GuildFeature::InviteSplash,
+            "VIP_REGIONS" => GuildFeature::VipRegions,
+            "VANITY_URL" => GuildFeature::VanityUrl,
+            "VERIFIED" => GuildFeature::Verified,
+            "PARTNERED" => GuildFeature::Partnered,
+            "PUBLIC" => GuildFeature::Public,
+            "COMMERCE" => GuildFeature::Commerce,
+            "NEWS" => GuildFeature::News,
+            "DISCOVERABLE" => GuildFeature::Discoverable,
+            "FEATURABLE" => GuildFeature::Featurable,
+            "ANIMATED_ICON" => GuildFeature::AnimatedIcon,
+            "BANNER" => GuildFeature::Banner,
+            _ => GuildFeature::Unknown(feature),
+        }
+    }
+}
+
+impl From<GuildFeature> for String {
+    fn from(feature: GuildFeature) -> Self {
+        match feature {
+            GuildFeature::Unknown(feature) => feature,
+            feature => feature.as_str().to_owned(),
+        }
+    }
+}
+
+int_enum! {
+    /// The required level of multi-factor authentication required for a guild.
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum MfaLevel: u8 {
+        /// Multi-factor authentication is not required.
+        None = 0,
+        /// Multi-factor authentication is required for members to take
+        /// moderation actions (eg. kick, ban, delete messages).
+        Elevated = 1,
+    }
 }
 
 impl Default for MfaLevel {
@@ -138,19 +203,20 @@ impl Default for MfaLevel {
     }
 }
 
-/// The tier of premium for a guild, provided by Nitro boosts.
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub enum PremiumTier {
-    /// Not premium.
-    None = 0,
-    /// Tier 1.
-    Tier1 = 1,
-    /// Tier 2.
-    Tier2 = 2,
-    /// Tier 3.
-    Tier3 = 3,
+int_enum! {
+    /// The tier of premium for a guild, provided by Nitro boosts.
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum PremiumTier: u8 {
+        /// Not premium.
+        None = 0,
+        /// Tier 1.
+        Tier1 = 1,
+        /// Tier 2.
+        Tier2 = 2,
+        /// Tier 3.
+        Tier3 = 3,
+    }
 }
 
 impl Default for PremiumTier {
@@ -178,6 +244,40 @@ pub struct PartialGuild {
     pub permissions: Option<Permissions>,
 }
 
+/// A ban of a user from a guild.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Ban {
+    /// The reason for the ban.
+    pub reason: Option<String>,
+    /// The banned user.
+    pub user: User,
+}
+
+/// The embed widget settings of a guild.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildEmbed {
+    /// Whether the embed is enabled.
+    pub enabled: bool,
+    /// The ID of the channel the embed generates invites to.
+    pub channel_id: Option<ChannelId>,
+}
+
+/// A guild that is unavailable to the client user, usually due to an outage.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UnavailableGuild {
+    /// The ID of the guild.
+    pub id: GuildId,
+    /// Whether the guild is unavailable.
+    ///
+    /// If this is `false` in a `GUILD_DELETE` event, the client user was
+    /// removed from the guild.
+    #[serde(default)]
+    pub unavailable: bool,
+}
+
 /// A guild in Discord represents an isolated collection of users and channels,
 /// and are often referred to as "servers" in the UI.
 #[non_exhaustive]
@@ -283,6 +383,9 @@ pub struct Guild {
 wrap!(Guild => mut guild: PartialGuild);
 
 impl_eq_fields!(PartialGuild: [id, name, icon, owner, permissions]);
+impl_eq_fields!(UnavailableGuild: [id, unavailable]);
+impl_eq_fields!(Ban: [reason, user]);
+impl_eq_fields!(GuildEmbed: [enabled, channel_id]);
 impl_eq_fields!(Guild: (a, b) => {
     assert_eq_fields!(a, b, [
         guild,
@@ -329,6 +432,8 @@ mod tests {
 
     use serde_json::json;
 
+    use crate::model::user::Discriminator;
+
     use super::*;
 
     #[test]
@@ -402,4 +507,72 @@ mod tests {
         let deserialized = Guild::deserialize(&value).unwrap();
         assert_eq_fields!(guild, deserialized);
     }
+
+    #[test]
+    fn test_deserialize_ban() {
+        let value = json!({
+          "reason": "mentioning b1nzy",
+          "user": {
+            "username": "Mason",
+            "discriminator": "9999",
+            "id": "53908099506183680",
+            "avatar": "a_bab14f271d565501444b2ca3be944b25"
+          }
+        });
+        let ban = Ban {
+            reason: Some("mentioning b1nzy".to_owned()),
+            user: User {
+                id: UserId::from(53908099506183680),
+                name: "Mason".to_owned(),
+                discriminator: Discriminator::new(9999).unwrap(),
+                avatar: Some("a_bab14f271d565501444b2ca3be944b25".to_owned()),
+                bot: false,
+                system: false,
+            },
+        };
+
+        let deserialized = Ban::deserialize(&value).unwrap();
+        assert_eq_fields!(ban, deserialized);
+    }
+
+    #[test]
+    fn test_deserialize_guild_embed() {
+        let value = json!({
+          "enabled": true,
+          "channel_id": "41771983444115456"
+        });
+        let embed = GuildEmbed {
+            enabled: true,
+            channel_id: Some(ChannelId::from(41771983444115456)),
+        };
+
+        let deserialized = GuildEmbed::deserialize(&value).unwrap();
+        assert_eq_fields!(embed, deserialized);
+    }
+
+    #[test]
+    fn test_guild_feature_unknown() {
+        let value = json!(["NEWS", "NEW_FEATURE"]);
+        let features = vec![
+            GuildFeature::News,
+            GuildFeature::Unknown("NEW_FEATURE".to_owned()),
+        ];
+
+        let deserialized: Vec<GuildFeature> = Deserialize::deserialize(&value).unwrap();
+        assert_eq!(features, deserialized);
+        assert_eq!(value, serde_json::to_value(&features).unwrap());
+    }
+
+    #[test]
+    fn test_guild_levels_unknown() {
+        let value = json!([1, 5]);
+        let levels = vec![VerificationLevel::Low, VerificationLevel::Unknown(5)];
+
+        let deserialized: Vec<VerificationLevel> = Deserialize::deserialize(&value).unwrap();
+        assert_eq!(levels, deserialized);
+        assert_eq!(value, serde_json::to_value(&levels).unwrap());
+
+        let tier: PremiumTier = Deserialize::deserialize(&json!(4)).unwrap();
+        assert_eq!(tier, PremiumTier::Unknown(4));
+    }
 }
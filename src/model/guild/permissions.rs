@@ -0,0 +1,530 @@
+use std::cmp::Ordering;
+
+use crate::model::channel::guild::GuildChannel;
+use crate::model::channel::permissions::{OverwriteId, PermissionOverwrite};
+use crate::model::id::RoleId;
+use crate::model::permissions::Permissions;
+
+use super::{Guild, Member, Role};
+
+/// Permissions that are implicitly denied in text channels if a member cannot
+/// send messages.
+const SEND_MESSAGES_DEPENDENT: Permissions = Permissions::from_bits_truncate(
+    Permissions::SEND_TTS_MESSAGES.bits()
+        | Permissions::MENTION_EVERYONE.bits()
+        | Permissions::EMBED_LINKS.bits()
+        | Permissions::ATTACH_FILES.bits(),
+);
+
+/// Permissions that are implicitly denied in voice channels if a member cannot
+/// connect.
+const CONNECT_DEPENDENT: Permissions = Permissions::from_bits_truncate(
+    Permissions::SPEAK.bits()
+        | Permissions::MUTE_MEMBERS.bits()
+        | Permissions::DEAFEN_MEMBERS.bits()
+        | Permissions::MOVE_MEMBERS.bits()
+        | Permissions::USE_VAD.bits()
+        | Permissions::PRIORITY_SPEAKER.bits()
+        | Permissions::STREAM.bits(),
+);
+
+impl Guild {
+    /// The ID of the `@everyone` role, which has the same ID as the guild.
+    pub fn everyone_role_id(&self) -> RoleId {
+        RoleId::from(*self.id)
+    }
+
+    /// Calculates the permissions of a member in the guild, excluding channel
+    /// permission overwrites.
+    ///
+    /// The owner of the guild and members with the [`ADMINISTRATOR`] permission
+    /// have all permissions.
+    ///
+    /// [`ADMINISTRATOR`]: ../permissions/struct.Permissions.html#associatedconstant.ADMINISTRATOR
+    pub fn member_permissions(&self, member: &Member) -> Permissions {
+        if member.user.id == self.owner_id {
+            return Permissions::all();
+        }
+
+        let mut permissions = self
+            .roles
+            .get(&self.everyone_role_id())
+            .map(|role| role.permissions)
+            .unwrap_or_default();
+
+        for role in member.roles.iter().filter_map(|id| self.roles.get(id)) {
+            permissions |= role.permissions;
+        }
+
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+
+        permissions
+    }
+
+    /// Calculates the permissions of a member in a channel of the guild.
+    ///
+    /// The permission overwrites of the channel are applied in order: the
+    /// `@everyone` role overwrite, the overwrites of the roles of the member
+    /// and the overwrite of the member.
+    ///
+    /// Permissions that depend on other permissions are implicitly denied, for
+    /// example members without the [`VIEW_CHANNEL`] permission have no
+    /// permissions in the channel.
+    ///
+    /// [`VIEW_CHANNEL`]: ../permissions/struct.Permissions.html#associatedconstant.VIEW_CHANNEL
+    pub fn member_permissions_in(&self, channel: &GuildChannel, member: &Member) -> Permissions {
+        let mut permissions = self.member_permissions(member);
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+
+        let overwrites = channel.permission_overwrites();
+        let find = |id: OverwriteId| overwrites.iter().find(|overwrite| overwrite.id == id);
+
+        if let Some(overwrite) = find(OverwriteId::Role(self.everyone_role_id())) {
+            apply(&mut permissions, overwrite);
+        }
+
+        let mut roles = PermissionOverwrite::empty(self.everyone_role_id());
+        for overwrite in overwrites {
+            if let OverwriteId::Role(id) = overwrite.id {
+                if member.roles.contains(&id) {
+                    roles.allow |= overwrite.allow;
+                    roles.deny |= overwrite.deny;
+                }
+            }
+        }
+        apply(&mut permissions, &roles);
+
+        if let Some(overwrite) = find(OverwriteId::User(member.user.id)) {
+            apply(&mut permissions, overwrite);
+        }
+
+        if !permissions.contains(Permissions::VIEW_CHANNEL) {
+            return Permissions::empty();
+        }
+
+        match channel {
+            GuildChannel::Text(_) | GuildChannel::News(_)
+                if !permissions.contains(Permissions::SEND_MESSAGES) =>
+            {
+                permissions.remove(SEND_MESSAGES_DEPENDENT);
+            }
+            GuildChannel::Voice(_) if !permissions.contains(Permissions::CONNECT) => {
+                permissions.remove(CONNECT_DEPENDENT);
+            }
+            _ => {}
+        }
+
+        permissions
+    }
+}
+
+impl Guild {
+    /// Returns the highest role of a member in the role hierarchy, or `None` if
+    /// the member has no roles other than `@everyone`.
+    pub fn highest_role(&self, member: &Member) -> Option<&Role> {
+        member
+            .roles
+            .iter()
+            .filter_map(|id| self.roles.get(id))
+            .max_by(|a, b| a.hierarchy_cmp(b))
+    }
+
+    /// Checks whether the `actor` member is above the `target` member in the
+    /// role hierarchy, as required to kick, ban or edit the nickname of the
+    /// target.
+    ///
+    /// The owner of the guild can manage all other members, and cannot be
+    /// managed by anyone. This does not check that the actor has the required
+    /// permissions for an action, see [`member_permissions`].
+    ///
+    /// [`member_permissions`]: #method.member_permissions
+    pub fn can_manage(&self, actor: &Member, target: &Member) -> bool {
+        if actor.user.id == target.user.id || target.user.id == self.owner_id {
+            return false;
+        }
+        if actor.user.id == self.owner_id {
+            return true;
+        }
+
+        match (self.highest_role(actor), self.highest_role(target)) {
+            (Some(actor), Some(target)) => actor.hierarchy_cmp(target) == Ordering::Greater,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Checks whether the `actor` member is able to assign or remove a role
+    /// from members.
+    ///
+    /// Roles managed by an integration and the `@everyone` role cannot be
+    /// assigned. Otherwise the owner of the guild can assign any role, and
+    /// other members can only assign roles below their highest role. This does
+    /// not check that the actor has the [`MANAGE_ROLES`] permission.
+    ///
+    /// [`MANAGE_ROLES`]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_ROLES
+    pub fn can_assign_role(&self, actor: &Member, role: &Role) -> bool {
+        if role.managed || role.id == self.everyone_role_id() {
+            return false;
+        }
+        if actor.user.id == self.owner_id {
+            return true;
+        }
+
+        match self.highest_role(actor) {
+            Some(highest) => highest.hierarchy_cmp(role) == Ordering::Greater,
+            None => false,
+        }
+    }
+}
+
+/// Applies a permission overwrite, denied permissions are removed before
+/// allowed permissions are added.
+fn apply(permissions: &mut Permissions, overwrite: &PermissionOverwrite) {
+    permissions.remove(overwrite.deny);
+    permissions.insert(overwrite.allow);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    const GUILD_ID: u64 = 41771983423143937;
+    const OWNER_ID: u64 = 80351110224678912;
+    const MEMBER_ID: u64 = 53908232506183680;
+    const MODERATOR_ROLE: u64 = 41771983423143936;
+    const MUTED_ROLE: u64 = 41771983423143935;
+    const ADMIN_ROLE: u64 = 41771983423143934;
+    const BOT_ROLE: u64 = 41771983423143933;
+
+    fn role(id: u64, position: usize, permissions: Permissions, managed: bool) -> Value {
+        json!({
+            "id": id.to_string(),
+            "name": "role",
+            "color": 0,
+            "hoist": false,
+            "position": position,
+            "permissions": permissions.bits(),
+            "managed": managed,
+            "mentionable": false
+        })
+    }
+
+    fn guild() -> Guild {
+        let everyone = Permissions::VIEW_CHANNEL
+            | Permissions::SEND_MESSAGES
+            | Permissions::EMBED_LINKS
+            | Permissions::CONNECT
+            | Permissions::SPEAK;
+
+        Guild::deserialize(json!({
+            "id": GUILD_ID.to_string(),
+            "name": "Guild",
+            "icon": null,
+            "splash": null,
+            "owner_id": OWNER_ID.to_string(),
+            "region": "us-east",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "verification_level": 0,
+            "default_message_notifications": 0,
+            "explicit_content_filter": 0,
+            "mfa_level": 0,
+            "application_id": null,
+            "system_channel_id": null,
+            "vanity_url_code": null,
+            "description": null,
+            "banner": null,
+            "roles": [
+                role(GUILD_ID, 0, everyone, false),
+                role(MUTED_ROLE, 1, Permissions::empty(), false),
+                role(
+                    MODERATOR_ROLE,
+                    2,
+                    Permissions::KICK_MEMBERS | Permissions::MANAGE_MESSAGES,
+                    false
+                ),
+                // Ties with the moderator role, but is higher due to the lower ID.
+                role(BOT_ROLE, 2, Permissions::MANAGE_ROLES, true),
+                role(ADMIN_ROLE, 3, Permissions::ADMINISTRATOR, false),
+            ],
+            "emojis": [],
+            "features": []
+        }))
+        .unwrap()
+    }
+
+    fn member(id: u64, roles: &[u64]) -> Member {
+        let roles: Vec<String> = roles.iter().map(u64::to_string).collect();
+
+        Member::deserialize(json!({
+            "user": {
+                "id": id.to_string(),
+                "username": "Member",
+                "discriminator": "0001",
+                "avatar": null
+            },
+            "roles": roles,
+            "joined_at": "2015-04-26T06:26:56.936000+00:00",
+            "deaf": false,
+            "mute": false
+        }))
+        .unwrap()
+    }
+
+    fn channel(kind: u8, overwrites: Value) -> GuildChannel {
+        GuildChannel::deserialize(json!({
+            "id": "41771983423143938",
+            "guild_id": GUILD_ID.to_string(),
+            "name": "channel",
+            "type": kind,
+            "position": 0,
+            "permission_overwrites": overwrites,
+            "nsfw": false,
+            "bitrate": 64000,
+            "user_limit": 0,
+            "parent_id": null
+        }))
+        .unwrap()
+    }
+
+    fn overwrite(id: u64, kind: &str, allow: Permissions, deny: Permissions) -> Value {
+        json!({
+            "id": id.to_string(),
+            "type": kind,
+            "allow": allow.bits(),
+            "deny": deny.bits()
+        })
+    }
+
+    #[test]
+    fn test_member_permissions() {
+        let guild = guild();
+
+        let member = member(MEMBER_ID, &[MODERATOR_ROLE]);
+        assert_eq!(
+            guild.member_permissions(&member),
+            Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::EMBED_LINKS
+                | Permissions::CONNECT
+                | Permissions::SPEAK
+                | Permissions::KICK_MEMBERS
+                | Permissions::MANAGE_MESSAGES
+        );
+    }
+
+    #[test]
+    fn test_member_permissions_admin_and_owner() {
+        let guild = guild();
+
+        let admin = member(MEMBER_ID, &[ADMIN_ROLE]);
+        assert_eq!(guild.member_permissions(&admin), Permissions::all());
+
+        let owner = member(OWNER_ID, &[]);
+        assert_eq!(guild.member_permissions(&owner), Permissions::all());
+
+        // Overwrites do not apply to administrators or the owner.
+        let channel = channel(
+            0,
+            json!([overwrite(
+                GUILD_ID,
+                "role",
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL
+            )]),
+        );
+        assert_eq!(
+            guild.member_permissions_in(&channel, &admin),
+            Permissions::all()
+        );
+        assert_eq!(
+            guild.member_permissions_in(&channel, &owner),
+            Permissions::all()
+        );
+    }
+
+    #[test]
+    fn test_overwrite_order() {
+        let guild = guild();
+        let member = member(MEMBER_ID, &[MODERATOR_ROLE, MUTED_ROLE]);
+
+        let channel = channel(
+            0,
+            json!([
+                // @everyone cannot embed links or send messages.
+                overwrite(
+                    GUILD_ID,
+                    "role",
+                    Permissions::empty(),
+                    Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES
+                ),
+                // Role allows take precedence over role denies.
+                overwrite(
+                    MUTED_ROLE,
+                    "role",
+                    Permissions::empty(),
+                    Permissions::ADD_REACTIONS | Permissions::SEND_MESSAGES
+                ),
+                overwrite(
+                    MODERATOR_ROLE,
+                    "role",
+                    Permissions::SEND_MESSAGES | Permissions::ADD_REACTIONS,
+                    Permissions::MANAGE_MESSAGES
+                ),
+                // The member overwrite is applied last.
+                overwrite(
+                    MEMBER_ID,
+                    "member",
+                    Permissions::EMBED_LINKS | Permissions::MANAGE_MESSAGES,
+                    Permissions::ADD_REACTIONS
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            guild.member_permissions_in(&channel, &member),
+            Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::EMBED_LINKS
+                | Permissions::CONNECT
+                | Permissions::SPEAK
+                | Permissions::KICK_MEMBERS
+                | Permissions::MANAGE_MESSAGES
+        );
+    }
+
+    #[test]
+    fn test_implicit_view_channel() {
+        let guild = guild();
+        let member = member(MEMBER_ID, &[MODERATOR_ROLE]);
+
+        let channel = channel(
+            0,
+            json!([overwrite(
+                MEMBER_ID,
+                "member",
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL
+            )]),
+        );
+        assert_eq!(
+            guild.member_permissions_in(&channel, &member),
+            Permissions::empty()
+        );
+    }
+
+    #[test]
+    fn test_implicit_send_messages() {
+        let guild = guild();
+        let member = member(MEMBER_ID, &[]);
+
+        let channel = channel(
+            0,
+            json!([overwrite(
+                GUILD_ID,
+                "role",
+                Permissions::empty(),
+                Permissions::SEND_MESSAGES
+            )]),
+        );
+        assert_eq!(
+            guild.member_permissions_in(&channel, &member),
+            Permissions::VIEW_CHANNEL | Permissions::CONNECT | Permissions::SPEAK
+        );
+    }
+
+    #[test]
+    fn test_implicit_connect() {
+        let guild = guild();
+        let member = member(MEMBER_ID, &[]);
+
+        let channel = channel(
+            2,
+            json!([overwrite(
+                GUILD_ID,
+                "role",
+                Permissions::empty(),
+                Permissions::CONNECT
+            )]),
+        );
+        assert_eq!(
+            guild.member_permissions_in(&channel, &member),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS
+        );
+    }
+
+    #[test]
+    fn test_highest_role() {
+        let guild = guild();
+
+        let highest = |roles: &[u64]| {
+            let member = member(MEMBER_ID, roles);
+            guild.highest_role(&member).map(|role| role.id)
+        };
+
+        assert_eq!(
+            highest(&[MUTED_ROLE, ADMIN_ROLE, MODERATOR_ROLE]),
+            Some(RoleId::from(ADMIN_ROLE))
+        );
+        assert_eq!(
+            highest(&[MODERATOR_ROLE, BOT_ROLE]),
+            Some(RoleId::from(BOT_ROLE))
+        );
+        assert_eq!(highest(&[]), None);
+    }
+
+    #[test]
+    fn test_can_manage() {
+        let guild = guild();
+
+        let owner = member(OWNER_ID, &[]);
+        let admin = member(1, &[ADMIN_ROLE]);
+        let moderator = member(2, &[MODERATOR_ROLE]);
+        let bot = member(3, &[BOT_ROLE]);
+        let muted = member(4, &[MUTED_ROLE]);
+        let everyone = member(5, &[]);
+
+        assert!(guild.can_manage(&owner, &admin));
+        assert!(!guild.can_manage(&admin, &owner));
+        assert!(!guild.can_manage(&owner, &owner));
+
+        assert!(guild.can_manage(&admin, &moderator));
+        assert!(!guild.can_manage(&moderator, &admin));
+
+        // Ties in position are broken by role ID.
+        assert!(guild.can_manage(&bot, &moderator));
+        assert!(!guild.can_manage(&moderator, &bot));
+
+        assert!(guild.can_manage(&muted, &everyone));
+        assert!(!guild.can_manage(&everyone, &muted));
+        assert!(!guild.can_manage(&everyone, &member(6, &[])));
+        assert!(!guild.can_manage(&moderator, &moderator));
+    }
+
+    #[test]
+    fn test_can_assign_role() {
+        let guild = guild();
+        let role = |id| &guild.roles[&RoleId::from(id)];
+
+        let owner = member(OWNER_ID, &[]);
+        let admin = member(1, &[ADMIN_ROLE]);
+        let moderator = member(2, &[MODERATOR_ROLE]);
+
+        assert!(guild.can_assign_role(&owner, role(ADMIN_ROLE)));
+        assert!(guild.can_assign_role(&admin, role(MODERATOR_ROLE)));
+        assert!(guild.can_assign_role(&moderator, role(MUTED_ROLE)));
+        assert!(!guild.can_assign_role(&moderator, role(MODERATOR_ROLE)));
+        assert!(!guild.can_assign_role(&moderator, role(ADMIN_ROLE)));
+
+        // Managed roles and @everyone cannot be assigned by anyone.
+        assert!(!guild.can_assign_role(&owner, role(BOT_ROLE)));
+        assert!(!guild.can_assign_role(&owner, role(GUILD_ID)));
+    }
+}
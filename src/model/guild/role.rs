@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use serde::{Deserialize, Serialize};
 
 use crate::model::color::Color;
@@ -34,6 +36,18 @@ pub struct Role {
     pub mentionable: bool,
 }
 
+impl Role {
+    /// Compares the position of two roles in the role hierarchy.
+    ///
+    /// Roles with a higher position are higher in the hierarchy, ties are
+    /// broken by the ID of the role, with the older role being higher.
+    pub fn hierarchy_cmp(&self, other: &Role) -> Ordering {
+        self.position
+            .cmp(&other.position)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
 impl_eq_fields!(Role: [
     id,
     name,
@@ -11,9 +11,12 @@ use crate::model::channel::guild::{
 };
 use crate::model::channel::message::Attachment;
 use crate::model::channel::{DMChannel, Group, Message};
-use crate::model::guild::{CustomEmoji, Emoji, Guild, PartialGuild, Role};
+use crate::model::guild::{
+    AuditLogEntry, CustomEmoji, Emoji, Guild, Integration, PartialGuild, PartialIntegration, Role,
+};
 use crate::model::snowflake::{Snowflake, ToSnowflake};
 use crate::model::user::{ClientUser, User};
+use crate::model::webhook::Webhook;
 
 macro_rules! id_type {
     ($(
@@ -86,7 +89,7 @@ id_type! {
 
     /// The ID of an [`AuditLogEntry`].
     ///
-    /// [`AuditLogEntry`]: TODO
+    /// [`AuditLogEntry`]: ../guild/struct.AuditLogEntry.html
     AuditLogEntryId;
 
     /// The ID of a [`Channel`].
@@ -106,7 +109,7 @@ id_type! {
 
     /// The ID of an [`Integration`].
     ///
-    /// [`Integration`]: TODO
+    /// [`Integration`]: ../guild/struct.Integration.html
     IntegrationId;
 
     /// The ID of a [`Message`].
@@ -126,10 +129,22 @@ id_type! {
 
     /// The ID of a [`Webhook`].
     ///
-    /// [`Webhook`]: TODO
+    /// [`Webhook`]: ../webhook/struct.Webhook.html
     WebhookId;
 }
 
+impl GuildId {
+    /// Returns the ID of the shard that receives events for the guild, given
+    /// the total number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is `0`.
+    pub fn shard_id(self, shards: u64) -> u64 {
+        (u64::from(self.0) >> 22) % shards
+    }
+}
+
 pub(crate) mod private {
     pub trait Sealed {}
 }
@@ -165,14 +180,18 @@ macro_rules! impl_to_id {
 
 impl_to_id! {
     Attachment => id: AttachmentId;
+    AuditLogEntry => id: AuditLogEntryId;
     Emoji => id: EmojiId;
     CustomEmoji => id: EmojiId;
     Guild => id: GuildId;
+    Integration => id: IntegrationId;
+    PartialIntegration => id: IntegrationId;
     PartialGuild => id: GuildId;
     Message => id: MessageId;
     Role => id: RoleId;
     User => id: UserId;
     ClientUser => id: UserId;
+    Webhook => id: WebhookId;
 }
 
 impl_to_id! {
@@ -0,0 +1,349 @@
+//! Models related to invites.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::model::channel::ChannelType;
+use crate::model::guild::{GuildFeature, VerificationLevel};
+use crate::model::id::{ChannelId, GuildId};
+use crate::model::user::User;
+
+/// The prefixes of invite links, followed by the invite code.
+///
+/// The first matching prefix is used, so a prefix must come before any shorter
+/// prefix it starts with.
+const INVITE_PREFIXES: &[&str] = &[
+    "discord.gg/invite/",
+    "discord.gg/",
+    "discord.com/invite/",
+    "discordapp.com/invite/",
+    "ptb.discord.com/invite/",
+    "ptb.discordapp.com/invite/",
+    "canary.discord.com/invite/",
+    "canary.discordapp.com/invite/",
+];
+
+/// A guild with partial information, as included in an [`Invite`].
+///
+/// [`Invite`]: struct.Invite.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InviteGuild {
+    /// The ID of the guild.
+    pub id: GuildId,
+    /// The name of the guild.
+    pub name: String,
+    /// The hash of the guild splash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub splash: Option<String>,
+    /// The hash of the guild banner.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    /// The description of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The hash of the guild icon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// The set of enabled guild features.
+    #[serde(default)]
+    pub features: HashSet<GuildFeature>,
+    /// The required verification level of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_level: Option<VerificationLevel>,
+    /// The vanity URL code of the guild.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vanity_url_code: Option<String>,
+}
+
+/// A channel with partial information, as included in an [`Invite`].
+///
+/// [`Invite`]: struct.Invite.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InviteChannel {
+    /// The ID of the channel.
+    pub id: ChannelId,
+    /// The name of the channel.
+    pub name: String,
+    /// The type of the channel.
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+}
+
+/// A code that when used, adds a user to a guild or group channel.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Invite {
+    /// The unique invite code.
+    pub code: String,
+    /// The guild the invite is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild: Option<InviteGuild>,
+    /// The channel the invite is for.
+    pub channel: InviteChannel,
+    /// The user who created the invite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inviter: Option<User>,
+    /// The user whose stream the invite is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_user: Option<User>,
+    /// The approximate number of online members in the guild.
+    ///
+    /// This is only present when the invite is fetched with counts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approximate_presence_count: Option<u64>,
+    /// The approximate number of total members in the guild.
+    ///
+    /// This is only present when the invite is fetched with counts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approximate_member_count: Option<u64>,
+}
+
+/// An [`Invite`] with additional information about its usage.
+///
+/// [`Invite`]: struct.Invite.html
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InviteMetadata {
+    #[serde(flatten)]
+    invite: Invite,
+    /// The number of times the invite has been used.
+    pub uses: u64,
+    /// The maximum number of times the invite can be used, `0` for unlimited.
+    pub max_uses: u64,
+    /// The duration in seconds after which the invite expires, `0` for never.
+    pub max_age: u64,
+    /// Whether the invite only grants temporary membership.
+    pub temporary: bool,
+    /// When the invite was created.
+    pub created_at: DateTime<FixedOffset>,
+}
+wrap!(InviteMetadata => mut invite: Invite);
+
+impl_eq_fields!(InviteGuild: [
+    id,
+    name,
+    splash,
+    banner,
+    description,
+    icon,
+    features,
+    verification_level,
+    vanity_url_code,
+]);
+impl_eq_fields!(InviteChannel: [id, name, kind]);
+impl_eq_fields!(Invite: [
+    code,
+    guild,
+    channel,
+    inviter,
+    target_user,
+    approximate_presence_count,
+    approximate_member_count,
+]);
+impl_eq_fields!(InviteMetadata: [invite, uses, max_uses, max_age, temporary, created_at]);
+
+/// Finds the codes of invite links in text, such as the content of a message.
+///
+/// Links of the forms `discord.gg/{code}`, `discord.com/invite/{code}` and
+/// `discordapp.com/invite/{code}` are found, with or without a scheme.
+///
+/// # Examples
+///
+/// ```
+/// use strife::model::invite::find_invite_codes;
+///
+/// let text = "Join us at https://discord.gg/rust or discordapp.com/invite/abc-123!";
+/// let codes: Vec<&str> = find_invite_codes(text).collect();
+///
+/// assert_eq!(codes, ["rust", "abc-123"]);
+/// ```
+pub fn find_invite_codes(text: &str) -> InviteCodes<'_> {
+    InviteCodes { text, pos: 0 }
+}
+
+/// An iterator over the codes of invite links in text.
+///
+/// This is created by [`find_invite_codes`].
+///
+/// [`find_invite_codes`]: fn.find_invite_codes.html
+#[derive(Clone, Debug)]
+pub struct InviteCodes<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Iterator for InviteCodes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+
+        while self.pos < bytes.len() {
+            let start = self.pos;
+            self.pos += 1;
+
+            // Links must not be preceded by part of a word, eg. `notdiscord.gg`.
+            if start > 0 && is_code_byte(bytes[start - 1]) {
+                continue;
+            }
+
+            let prefix = INVITE_PREFIXES.iter().find(|prefix| {
+                bytes.len() - start >= prefix.len()
+                    && bytes[start..start + prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+            });
+
+            if let Some(prefix) = prefix {
+                let code_start = start + prefix.len();
+                let code_len = bytes[code_start..]
+                    .iter()
+                    .take_while(|&&b| is_code_byte(b))
+                    .count();
+
+                if code_len > 0 {
+                    self.pos = code_start + code_len;
+                    return Some(&self.text[code_start..self.pos]);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn is_code_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-'
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::model::id::UserId;
+    use crate::model::user::Discriminator;
+
+    use super::*;
+
+    fn codes(text: &str) -> Vec<&str> {
+        find_invite_codes(text).collect()
+    }
+
+    #[test]
+    fn test_find_invite_codes() {
+        assert_eq!(codes("discord.gg/abc"), ["abc"]);
+        assert_eq!(codes("https://discord.gg/abc"), ["abc"]);
+        assert_eq!(codes("http://www.discord.gg/abc."), ["abc"]);
+        assert_eq!(codes("<https://DISCORD.GG/Abc>"), ["Abc"]);
+        assert_eq!(codes("https://discord.com/invite/abc-def"), ["abc-def"]);
+        assert_eq!(codes("https://discord.gg/invite/abc"), ["abc"]);
+        assert_eq!(codes("discordapp.com/invite/abc?foo=bar"), ["abc"]);
+        assert_eq!(codes("https://canary.discordapp.com/invite/abc"), ["abc"]);
+        assert_eq!(
+            codes("join discord.gg/one and discord.gg/two"),
+            ["one", "two"]
+        );
+    }
+
+    #[test]
+    fn test_find_invite_codes_none() {
+        assert!(codes("").is_empty());
+        assert!(codes("discord.gg/").is_empty());
+        assert!(codes("discord.gg").is_empty());
+        assert!(codes("notdiscord.gg/abc").is_empty());
+        assert!(codes("discord.com/channels/1/2").is_empty());
+        assert!(codes("discordapp.com/abc").is_empty());
+        assert!(codes("discord.gg/ abc").is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_invite() {
+        let value = json!({
+          "code": "0vCdhLbwjZZTWZLD",
+          "guild": {
+            "id": "165176875973476352",
+            "name": "CS:GO Fraggers Only",
+            "splash": null,
+            "icon": null,
+            "features": ["NEWS"],
+            "verification_level": 2
+          },
+          "channel": {
+            "id": "165176875973476352",
+            "name": "illuminati",
+            "type": 0
+          },
+          "inviter": {
+            "id": "80351110224678912",
+            "username": "Nelly",
+            "discriminator": "1337",
+            "avatar": null
+          },
+          "approximate_presence_count": 12,
+          "approximate_member_count": 48
+        });
+        let invite = Invite {
+            code: "0vCdhLbwjZZTWZLD".to_owned(),
+            guild: Some(InviteGuild {
+                id: GuildId::from(165176875973476352),
+                name: "CS:GO Fraggers Only".to_owned(),
+                splash: None,
+                banner: None,
+                description: None,
+                icon: None,
+                features: vec![GuildFeature::News].into_iter().collect(),
+                verification_level: Some(VerificationLevel::Medium),
+                vanity_url_code: None,
+            }),
+            channel: InviteChannel {
+                id: ChannelId::from(165176875973476352),
+                name: "illuminati".to_owned(),
+                kind: ChannelType::Text,
+            },
+            inviter: Some(User {
+                id: UserId::from(80351110224678912),
+                name: "Nelly".to_owned(),
+                discriminator: Discriminator::new(1337).unwrap(),
+                avatar: None,
+                bot: false,
+                system: false,
+            }),
+            target_user: None,
+            approximate_presence_count: Some(12),
+            approximate_member_count: Some(48),
+        };
+
+        let deserialized = Invite::deserialize(&value).unwrap();
+        assert_eq_fields!(invite, deserialized);
+    }
+
+    #[test]
+    fn test_deserialize_invite_metadata() {
+        let value = json!({
+          "code": "abc",
+          "channel": {
+            "id": "165176875973476352",
+            "name": "illuminati",
+            "type": 0
+          },
+          "uses": 3,
+          "max_uses": 10,
+          "max_age": 86400,
+          "temporary": false,
+          "created_at": "2016-03-31T19:15:39.954000+00:00"
+        });
+
+        let metadata = InviteMetadata::deserialize(&value).unwrap();
+        assert_eq!(metadata.code, "abc");
+        assert_eq!(metadata.uses, 3);
+        assert_eq!(metadata.max_uses, 10);
+        assert_eq!(metadata.max_age, 86400);
+        assert!(!metadata.temporary);
+        assert_eq!(
+            metadata.created_at,
+            DateTime::parse_from_rfc3339("2016-03-31T19:15:39.954+00:00").unwrap()
+        );
+    }
+}
@@ -7,8 +7,10 @@ pub mod color;
 pub mod gateway;
 pub mod guild;
 pub mod id;
+pub mod invite;
 pub mod misc;
 pub mod permissions;
 pub mod snowflake;
 pub mod user;
 pub mod voice;
+pub mod webhook;
@@ -7,8 +7,6 @@
 #![allow(clippy::unreadable_literal)]
 
 use bitflags::bitflags;
-use serde::de;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::model::utils::U64Visitor;
 
@@ -80,30 +78,4 @@ bitflags! {
     }
 }
 
-impl Serialize for Permissions {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u64(self.bits())
-    }
-}
-
-impl<'de> Deserialize<'de> for Permissions {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let bits = deserializer.deserialize_any(U64Visitor)?;
-        match Permissions::from_bits(bits) {
-            Some(perms) => Ok(perms),
-            None => {
-                let unknown: u64 = bits & !Permissions::all().bits();
-                Err(de::Error::custom(format!(
-                    "unknown permissions bits {:b} in {:b}",
-                    unknown, bits
-                )))
-            }
-        }
-    }
-}
+bitflags_serde!(Permissions: U64Visitor);
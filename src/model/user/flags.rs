@@ -1,6 +1,4 @@
 use bitflags::bitflags;
-use serde::de::{self, Deserialize, Deserializer};
-use serde::ser::{Serialize, Serializer};
 
 use crate::model::utils::U16Visitor;
 
@@ -38,30 +36,4 @@ bitflags! {
     }
 }
 
-impl Serialize for UserFlags {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u16(self.bits())
-    }
-}
-
-impl<'de> Deserialize<'de> for UserFlags {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let bits = deserializer.deserialize_any(U16Visitor)?;
-        match UserFlags::from_bits(bits) {
-            Some(perms) => Ok(perms),
-            None => {
-                let unknown: u16 = bits & !UserFlags::all().bits();
-                Err(de::Error::custom(format!(
-                    "unknown user flags bits {:b} in {:b}",
-                    unknown, bits
-                )))
-            }
-        }
-    }
-}
+bitflags_serde!(UserFlags: U16Visitor);
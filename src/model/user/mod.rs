@@ -68,17 +68,18 @@ pub struct User {
     pub system: bool,
 }
 
-/// The level of premium a [`User`] has.
-///
-/// [`User`]: struct.User.html
-#[non_exhaustive]
-#[int_enum::int_enum(u8)]
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub enum PremiumType {
-    /// Nitro Classic.
-    NitroClassic = 1,
-    /// Nitro.
-    Nitro = 2,
+int_enum! {
+    /// The level of premium a [`User`] has.
+    ///
+    /// [`User`]: struct.User.html
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum PremiumType: u8 {
+        /// Nitro Classic.
+        NitroClassic = 1,
+        /// Nitro.
+        Nitro = 2,
+    }
 }
 
 impl_eq_fields!(ClientUser: [user, mfa_enabled, locale, verified, email, flags, premium_type]);
@@ -5,6 +5,13 @@ pub fn is_false(b: &bool) -> bool {
     !b
 }
 
+/// Used in serde `skip_serializing_if` attribute.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+#[inline]
+pub fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
 /// Used in serde `default` attribute.
 #[inline]
 pub fn default_true() -> bool {
@@ -59,6 +66,43 @@ int_visitor! {
     pub U64Visitor: u64;
 }
 
+/// Serde mappings of optional integers that are sent by Discord as strings.
+pub mod serde_option_string_u64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::U64Visitor;
+
+    struct StringU64(u64);
+
+    impl<'de> Deserialize<'de> for StringU64 {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(U64Visitor).map(StringU64)
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value: Option<StringU64> = Deserialize::deserialize(deserializer)?;
+        Ok(value.map(|StringU64(n)| n))
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    pub fn serialize<S>(n: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match n {
+            Some(n) => serializer.collect_str(n),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
 /// Serde mappings of sequences of objects with Snowflake IDs to HashMaps keyed
 /// with by the Snowflake IDs.
 pub mod serde_id_map {
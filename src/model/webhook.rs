@@ -0,0 +1,105 @@
+//! Models related to webhooks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::id::{ChannelId, GuildId, WebhookId};
+use crate::model::user::User;
+
+int_enum! {
+    /// The type of a [`Webhook`].
+    ///
+    /// [`Webhook`]: struct.Webhook.html
+    #[non_exhaustive]
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub enum WebhookType: u8 {
+        /// A webhook that can post messages to a channel with a token.
+        Incoming = 1,
+        /// An internal webhook used by channel following to crosspost messages
+        /// into a channel.
+        ChannelFollower = 2,
+    }
+}
+
+/// A low-effort way to post messages to a channel, without requiring a bot
+/// user or authentication.
+#[non_exhaustive]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Webhook {
+    /// The ID of the webhook.
+    pub id: WebhookId,
+    /// The type of the webhook.
+    #[serde(rename = "type")]
+    pub kind: WebhookType,
+    /// The ID of the guild the webhook is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    /// The ID of the channel the webhook is for.
+    pub channel_id: ChannelId,
+    /// The user that created the webhook.
+    ///
+    /// This is not present when the webhook is fetched with its token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    /// The default name of the webhook.
+    pub name: Option<String>,
+    /// The default avatar hash of the webhook.
+    pub avatar: Option<String>,
+    /// The secure token of the webhook.
+    ///
+    /// This is only present for [`Incoming`] webhooks.
+    ///
+    /// [`Incoming`]: enum.WebhookType.html#variant.Incoming
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl_eq_fields!(Webhook: [id, kind, guild_id, channel_id, user, name, avatar, token]);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::model::id::UserId;
+    use crate::model::user::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let value = json!({
+          "name": "test webhook",
+          "type": 1,
+          "channel_id": "199737254929760256",
+          "token": "3d89bb7572e0fb30d8128367b3b1b44fecd1726de135cbe28a41f8b2f777c372ba2939e72279b94526ff5d1bd4358d65cf11",
+          "avatar": null,
+          "guild_id": "199737254929760256",
+          "id": "223704706495545344",
+          "user": {
+            "username": "test",
+            "discriminator": "7479",
+            "id": "190320984123768832",
+            "avatar": "b004ec1740a63ca06ae2e14c5cee11f3"
+          }
+        });
+        let webhook = Webhook {
+            id: WebhookId::from(223704706495545344),
+            kind: WebhookType::Incoming,
+            guild_id: Some(GuildId::from(199737254929760256)),
+            channel_id: ChannelId::from(199737254929760256),
+            user: Some(User {
+                id: UserId::from(190320984123768832),
+                name: "test".to_owned(),
+                discriminator: Discriminator::new(7479).unwrap(),
+                avatar: Some("b004ec1740a63ca06ae2e14c5cee11f3".to_owned()),
+                bot: false,
+                system: false,
+            }),
+            name: Some("test webhook".to_owned()),
+            avatar: None,
+            token: Some("3d89bb7572e0fb30d8128367b3b1b44fecd1726de135cbe28a41f8b2f777c372ba2939e72279b94526ff5d1bd4358d65cf11".to_owned()),
+        };
+
+        let deserialized = Webhook::deserialize(&value).unwrap();
+        assert_eq_fields!(webhook, deserialized);
+    }
+}
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 use serde_json::json;
-use strife::model::guild::PartialEmoji;
+use strife::model::guild::{CustomEmoji, EmojiParseError, PartialEmoji};
 
 #[test]
 fn test_deserialize_standard() {
@@ -46,3 +46,96 @@ fn test_serialize_custom() {
 
     assert_eq!(value, serde_json::to_value(&emoji).unwrap());
 }
+
+#[test]
+fn test_parse_custom() {
+    let emoji = PartialEmoji::custom(41771983429993937, "LUL", false);
+    let parsed: PartialEmoji = "<:LUL:41771983429993937>".parse().unwrap();
+
+    assert_eq!(emoji, parsed);
+    assert_eq!(parsed.name(), Some("LUL"));
+    assert!(!parsed.animated());
+}
+
+#[test]
+fn test_parse_custom_animated() {
+    let parsed: PartialEmoji = "<a:dance:123>".parse().unwrap();
+
+    assert_eq!(parsed, PartialEmoji::custom(123, "dance", true));
+    assert_eq!(parsed.name(), Some("dance"));
+    assert!(parsed.animated());
+}
+
+#[test]
+fn test_parse_round_trip() {
+    // Custom emojis in reaction events may have no name.
+    let mut nameless = CustomEmoji::new(41771983429993937, "LUL", false);
+    nameless.name = None;
+
+    let emojis = [
+        PartialEmoji::custom(41771983429993937, "LUL", false),
+        PartialEmoji::custom(41771983429993937, "LUL_2", true),
+        PartialEmoji::Custom(nameless),
+        PartialEmoji::standard("🔥"),
+        PartialEmoji::standard("👍🏽"),
+        PartialEmoji::standard("👨‍👩‍👧"),
+        PartialEmoji::standard("🇬🇧"),
+        PartialEmoji::standard("❤️"),
+        PartialEmoji::standard("#️⃣"),
+    ];
+
+    for emoji in emojis.iter() {
+        let parsed: PartialEmoji = emoji.to_string().parse().unwrap();
+        assert_eq!(emoji, &parsed);
+        assert_eq!(emoji.name(), parsed.name());
+        assert_eq!(emoji.animated(), parsed.animated());
+    }
+}
+
+#[test]
+fn test_parse_invalid() {
+    fn parse(s: &str) -> EmojiParseError {
+        s.parse::<PartialEmoji>().unwrap_err()
+    }
+
+    match parse("") {
+        EmojiParseError::Empty => {}
+        _ => panic!("expected empty emoji error"),
+    }
+    match parse("<:LUL>") {
+        EmojiParseError::InvalidCustom(_) => {}
+        _ => panic!("expected invalid custom emoji error"),
+    }
+    match parse("<LUL:123>") {
+        EmojiParseError::InvalidCustom(_) => {}
+        _ => panic!("expected invalid custom emoji error"),
+    }
+    match parse("<:LUL:123") {
+        EmojiParseError::InvalidCustom(_) => {}
+        _ => panic!("expected invalid custom emoji error"),
+    }
+    match parse("<:a b:123>") {
+        EmojiParseError::InvalidCustom(_) => {}
+        _ => panic!("expected invalid custom emoji error"),
+    }
+    match parse("<:LUL:abc>") {
+        EmojiParseError::InvalidId(_) => {}
+        _ => panic!("expected invalid emoji ID error"),
+    }
+    match parse("LUL") {
+        EmojiParseError::InvalidStandard(_) => {}
+        _ => panic!("expected invalid standard emoji error"),
+    }
+    match parse("🔥 fire") {
+        EmojiParseError::InvalidStandard(_) => {}
+        _ => panic!("expected invalid standard emoji error"),
+    }
+    match parse("1") {
+        EmojiParseError::InvalidStandard(_) => {}
+        _ => panic!("expected invalid standard emoji error"),
+    }
+    match parse("\u{FE0F}") {
+        EmojiParseError::InvalidStandard(_) => {}
+        _ => panic!("expected invalid standard emoji error"),
+    }
+}
@@ -0,0 +1,180 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use hyper::client::HttpConnector;
+use hyper::header::{AUTHORIZATION, USER_AGENT};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+
+use strife::http::{Http, HttpError, WebhookClient};
+use strife::model::id::{ChannelId, WebhookId};
+use strife::Error;
+
+/// Starts a mock API server, returning its base URL.
+fn mock_server() -> String {
+    let make_service = make_service_fn(|_| async {
+        Ok::<_, Infallible>(service_fn(|req: Request<Body>| async move {
+            Ok::<_, Infallible>(respond(req))
+        }))
+    });
+
+    let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(server);
+
+    format!("http://{}/api/v6", addr)
+}
+
+fn respond(req: Request<Body>) -> Response<Body> {
+    let header = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    };
+
+    // Webhooks are authenticated by the token in the URL.
+    if req.uri().path() == "/api/v6/webhooks/223704706495545344/webhook-token" {
+        return Response::new(Body::from(
+            r#"{
+              "id": "223704706495545344",
+              "type": 1,
+              "channel_id": "199737254929760256",
+              "name": "test webhook",
+              "avatar": null
+            }"#,
+        ));
+    }
+
+    if header(USER_AGENT) != "MockBot (https://example.com, 1.0)"
+        || header(AUTHORIZATION) != "Bot token"
+    {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from(r#"{"code": 0, "message": "401: Unauthorized"}"#))
+            .unwrap();
+    }
+
+    match req.uri().path() {
+        "/api/v6/users/@me" => Response::new(Body::from(
+            r#"{
+              "id": "80351110224678912",
+              "username": "Nelly",
+              "discriminator": "1337",
+              "avatar": null,
+              "bot": true,
+              "mfa_enabled": false
+            }"#,
+        )),
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from(
+                r#"{"code": 10003, "message": "Unknown Channel"}"#,
+            ))
+            .unwrap(),
+    }
+}
+
+fn client(base_url: &str) -> Http {
+    Http::builder()
+        .token("token")
+        .base_url(base_url)
+        .user_agent("MockBot (https://example.com, 1.0)")
+        .connector(HttpConnector::new())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_base_url() {
+    let base_url = mock_server();
+    let http = client(&base_url);
+
+    let user = http.get_current_user().await.unwrap();
+    assert_eq!(user.name, "Nelly");
+}
+
+#[tokio::test]
+async fn test_base_url_error() {
+    let base_url = mock_server();
+    let http = client(&base_url);
+
+    match http.get_channel(ChannelId::from(1234)).await {
+        Err(Error::HttpError(HttpError::UnsuccessfulRequest(response))) => {
+            assert_eq!(response.status, StatusCode::NOT_FOUND);
+            assert_eq!(response.url, format!("{}/channels/1234", base_url));
+            assert_eq!(response.error.unwrap().code, 10003);
+        }
+        _ => panic!("expected unsuccessful request"),
+    }
+}
+
+#[tokio::test]
+async fn test_user_agent() {
+    let base_url = mock_server();
+    let http = Http::builder()
+        .token("token")
+        .base_url(base_url)
+        .connector(HttpConnector::new())
+        .build()
+        .unwrap();
+
+    match http.get_current_user().await {
+        Err(Error::HttpError(HttpError::UnsuccessfulRequest(response))) => {
+            assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+        }
+        _ => panic!("expected unsuccessful request"),
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_request_circuit_breaker() {
+    let base_url = mock_server();
+    let http = Http::builder()
+        .token("invalid token")
+        .base_url(base_url)
+        .user_agent("MockBot (https://example.com, 1.0)")
+        .connector(HttpConnector::new())
+        .invalid_request_threshold(2)
+        .invalid_request_circuit_breaker(true)
+        .build()
+        .unwrap();
+
+    for _ in 0..2 {
+        match http.get_current_user().await {
+            Err(Error::HttpError(HttpError::UnsuccessfulRequest(response))) => {
+                assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+            }
+            _ => panic!("expected unsuccessful request"),
+        }
+    }
+    assert_eq!(http.invalid_request_count(), 2);
+
+    match http.get_current_user().await {
+        Err(Error::HttpError(HttpError::TooManyInvalidRequests { count: 2 })) => {}
+        _ => panic!("expected too many invalid requests error"),
+    }
+
+    // Requests needed to connect to the gateway are still sent.
+    match http.get_bot_gateway().await {
+        Err(Error::HttpError(HttpError::UnsuccessfulRequest(response))) => {
+            assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+        }
+        _ => panic!("expected unsuccessful request"),
+    }
+}
+
+#[tokio::test]
+async fn test_webhook_client_with_http() {
+    let base_url = mock_server();
+    let http = Http::builder()
+        .base_url(base_url)
+        .connector(HttpConnector::new())
+        .build()
+        .unwrap();
+
+    let webhook =
+        WebhookClient::with_http(http, WebhookId::from(223704706495545344), "webhook-token");
+    let webhook = webhook.get().await.unwrap();
+    assert_eq!(webhook.name.as_deref(), Some("test webhook"));
+}
@@ -32,9 +32,20 @@ fn test_deserialize() {
     assert_eq!(perms, Permissions::deserialize(&value).unwrap());
 }
 
+#[test]
+fn test_deserialize_unknown_bits() {
+    let value = json!(0x80000000u64 | 0x00000800);
+    let perms = Permissions::deserialize(&value).unwrap();
+
+    assert!(perms.contains(Permissions::SEND_MESSAGES));
+    assert_ne!(perms, Permissions::SEND_MESSAGES);
+    // Unknown bits round trip.
+    assert_eq!(value, serde_json::to_value(&perms).unwrap());
+}
+
 #[test]
 fn test_deserialize_invalid() {
-    let value = json!(0x00080000);
+    let value = json!("invalid");
     let err = Permissions::deserialize(&value);
 
     assert!(err.is_err());
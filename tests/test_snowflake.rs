@@ -1,5 +1,6 @@
 use serde::Deserialize;
 use serde_json::json;
+use strife::model::id::GuildId;
 use strife::model::snowflake::Snowflake;
 
 #[test]
@@ -19,3 +20,17 @@ fn test_deserialize() {
     let value = json!("80351110224678912");
     assert_eq!(snowflake, Snowflake::deserialize(&value).unwrap());
 }
+
+#[test]
+fn test_guild_shard_id() {
+    let guild_id = GuildId::from(41771983423143937);
+    assert_eq!(guild_id.shard_id(1), 0);
+    assert_eq!(guild_id.shard_id(4), 2);
+    assert_eq!(guild_id.shard_id(16), 6);
+}
+
+#[test]
+#[should_panic]
+fn test_guild_shard_id_no_shards() {
+    GuildId::from(41771983423143937).shard_id(0);
+}